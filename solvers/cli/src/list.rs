@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use common::{inventory::Tag, DaySolver, DaySolverImpl};
+use serde::Serialize;
+
+use crate::{answer_path, input_path, test_path, Part};
+
+#[derive(Debug, Serialize)]
+struct DayEntry {
+    year: u16,
+    day: u8,
+    solvers: Vec<SolverEntry>,
+    tests: bool,
+    input_cached: bool,
+    answers_known: AnswersKnown,
+}
+
+#[derive(Debug, Serialize)]
+struct SolverEntry {
+    label: Option<&'static str>,
+    kind: SolverKind,
+    title: Option<&'static str>,
+    description: Option<&'static str>,
+    tags: Vec<&'static str>,
+    complexity: Option<&'static str>,
+    author: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnswersKnown {
+    part_1: bool,
+    part_2: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+enum SolverKind {
+    Mono,
+    Dual,
+}
+
+impl From<&DaySolverImpl> for SolverKind {
+    fn from(implementation: &DaySolverImpl) -> Self {
+        match implementation {
+            DaySolverImpl::Mono(_) => SolverKind::Mono,
+            DaySolverImpl::Dual(_) => SolverKind::Dual,
+        }
+    }
+}
+
+impl std::fmt::Display for SolverKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SolverKind::Mono => "Mono",
+            SolverKind::Dual => "Dual",
+        })
+    }
+}
+
+pub(crate) fn list(
+    solvers: impl Iterator<Item = DaySolver>,
+    tags: &[Tag],
+    json: bool,
+) -> anyhow::Result<()> {
+    let mut days: Vec<DayEntry> = vec![];
+    for solver in solvers {
+        if !tags.iter().all(|t| solver.metadata.tags.contains(t)) {
+            continue;
+        }
+
+        let entry = match days.last_mut() {
+            Some(last) if last.year == solver.year && last.day == solver.day => last,
+            _ => {
+                days.push(day_entry(solver.year, solver.day));
+                days.last_mut().unwrap()
+            }
+        };
+
+        let metadata = solver.metadata;
+        entry.solvers.push(SolverEntry {
+            label: solver.label,
+            kind: SolverKind::from(&solver.implementation),
+            title: metadata.title,
+            description: metadata.description,
+            tags: metadata.tags.iter().map(Tag::name).collect(),
+            complexity: metadata.complexity,
+            author: metadata.author,
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&days)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            "Year", "Day", "Label", "Kind", "Title", "Tags", "Details", "Tests", "Input", "Answers",
+        ])
+        .add_rows(days.iter().flat_map(|entry| {
+            entry.solvers.iter().map(|solver| {
+                [
+                    entry.year.to_string(),
+                    entry.day.to_string(),
+                    solver.label.unwrap_or("-").to_string(),
+                    solver.kind.to_string(),
+                    solver.title.unwrap_or("-").to_string(),
+                    solver.tags.join(", "),
+                    details(solver),
+                    yes_no(entry.tests).to_string(),
+                    yes_no(entry.input_cached).to_string(),
+                    match (entry.answers_known.part_1, entry.answers_known.part_2) {
+                        (true, true) => "1, 2",
+                        (true, false) => "1",
+                        (false, true) => "2",
+                        (false, false) => "-",
+                    }
+                    .to_string(),
+                ]
+            })
+        }));
+
+    println!("{table}");
+
+    Ok(())
+}
+
+fn day_entry(year: u16, day: u8) -> DayEntry {
+    let tests = test_path(year, day);
+    DayEntry {
+        year,
+        day,
+        solvers: vec![],
+        tests: is_non_empty_file(&tests.join("input_1"))
+            && is_non_empty_file(&tests.join("answer_1")),
+        input_cached: is_non_empty_file(&input_path(year, day)),
+        answers_known: AnswersKnown {
+            part_1: is_non_empty_file(&answer_path(year, day, Part::One)),
+            part_2: is_non_empty_file(&answer_path(year, day, Part::Two)),
+        },
+    }
+}
+
+fn details(solver: &SolverEntry) -> String {
+    [
+        solver.description.map(|d| d.to_string()),
+        solver.complexity.map(|c| format!("complexity: {c}")),
+        solver.author.map(|a| format!("author: {a}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn is_non_empty_file(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .map(|m| m.is_file() && m.len() > 0)
+        .unwrap_or(false)
+}
+
+fn yes_no(b: bool) -> &'static str {
+    if b {
+        "✅"
+    } else {
+        "-"
+    }
+}
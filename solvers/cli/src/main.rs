@@ -6,39 +6,76 @@ use std::{
 };
 
 use anyhow::{bail, Context};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
-use common::{inventory::LabelQuery, DaySolver, PartResult, UnimplementedSolver};
+use common::{
+    inventory::{LabelQuery, SolverMetadata, Tag},
+    DaySolver, PartResult, UnimplementedSolver,
+};
 #[allow(unused_imports)]
 use common::{DaySolverImpl, DualDaySolver, MonoDaySolver};
 use maybe_shared::MaybeShared;
 use reqwest::blocking::Client;
 
+mod list;
 mod maybe_shared;
 mod scaffold_solver;
+mod submit;
 
 extern crate imports;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short, long, num_args = 1.., value_delimiter = ',')]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(short, long, global = true, num_args = 1.., value_delimiter = ',')]
     label: Option<Vec<String>>,
 
     #[arg(short, long)]
     test: bool,
 
+    /// Output results as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Let solvers print debugging output, such as annotated maps, on stderr
+    #[arg(long)]
+    debug: bool,
+
     /// Year to solve
-    #[arg(short, long, value_parser = clap::value_parser!(u16), num_args = 1.., value_delimiter = ',')]
+    #[arg(short, long, global = true, value_parser = clap::value_parser!(u16), num_args = 1.., value_delimiter = ',')]
     year: Option<Vec<u16>>,
 
     /// Day to solve
-    #[arg(short, long, value_parser = clap::value_parser!(u8).range(0..=24), num_args = 1.., value_delimiter = ',')]
+    #[arg(short, long, global = true, value_parser = clap::value_parser!(u8).range(0..=24), num_args = 1.., value_delimiter = ',')]
     day: Option<Vec<u8>>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List registered solvers along with their tests, inputs and answers
+    List {
+        /// Only list solvers having all of these tags
+        #[arg(long, num_args = 1.., value_delimiter = ',')]
+        tag: Option<Vec<Tag>>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Solve a single day on its real input and submit the answer of a part
+    Submit {
+        /// Part to submit
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+}
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    common::debug::set_enabled(args.debug);
 
     let solvers = common::inventory::Solvers::new().map_err(anyhow::Error::msg)?;
 
@@ -52,6 +89,32 @@ fn main() -> anyhow::Result<()> {
         None => LabelQuery::DefaultOnly,
     };
 
+    if let Some(Command::List { tag, json }) = args.command {
+        let label_query = match label_query {
+            LabelQuery::DefaultOnly => LabelQuery::All,
+            query => query,
+        };
+        let solvers = solvers.query(args.year.as_deref(), args.day.as_deref(), label_query);
+        return list::list(solvers, tag.as_deref().unwrap_or_default(), json);
+    }
+
+    if let Some(Command::Submit { part }) = args.command {
+        let (Some([year]), Some([day])) = (args.year.as_deref(), args.day.as_deref()) else {
+            bail!("A single year and day must be given to submit an answer")
+        };
+        let Some(solver) = solvers
+            .query(Some(&[*year]), Some(&[*day]), label_query)
+            .next()
+        else {
+            bail!("No matching solver found!")
+        };
+        let result = solve(&solver, false)?;
+        return match part {
+            1 => submit::submit(*year, *day, Part::One, &result.part_1),
+            _ => submit::submit(*year, *day, Part::Two, &result.part_2),
+        };
+    }
+
     let mut solvers: Vec<_> = solvers
         .query(args.year.as_deref(), args.day.as_deref(), label_query)
         .collect();
@@ -65,6 +128,7 @@ fn main() -> anyhow::Result<()> {
                 year: *year,
                 day: *day,
                 label: None,
+                metadata: &SolverMetadata::EMPTY,
                 implementation: UnimplementedSolver.to_day_solver_impl(),
             });
         } else {
@@ -85,6 +149,12 @@ fn main() -> anyhow::Result<()> {
         })
         .sum::<Duration>();
 
+    if args.json {
+        let json = results.iter().map(result_to_json).collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
@@ -170,11 +240,13 @@ fn solve(solver: &DaySolver, test: bool) -> anyhow::Result<DayResult> {
     };
 
     let part_1_validated = part_1
-        .resolved()
-        .and_then(|r| runner.validate(Part::One, &r));
+        .is_resolved()
+        .then(|| runner.validate(Part::One, &part_1))
+        .flatten();
     let part_2_validated = part_2
-        .resolved()
-        .and_then(|r| runner.validate(Part::Two, &r));
+        .is_resolved()
+        .then(|| runner.validate(Part::Two, &part_2))
+        .flatten();
 
     Ok(DayResult {
         day: solver.day,
@@ -188,10 +260,33 @@ fn solve(solver: &DaySolver, test: bool) -> anyhow::Result<DayResult> {
     })
 }
 
+fn input_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from("inputs")
+        .join(year.to_string())
+        .join(day.to_string())
+}
+
+fn answer_path(year: u16, day: u8, part: Part) -> PathBuf {
+    let part = match part {
+        Part::One => 1,
+        Part::Two => 2,
+    };
+    PathBuf::from("inputs")
+        .join(year.to_string())
+        .join(format!("{day}_answer_{part}"))
+}
+
+fn test_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from("tests")
+        .join(year.to_string())
+        .join(day.to_string())
+}
+
 fn get_online_io_driver(year: u16, day: u8) -> anyhow::Result<Box<dyn IODriver>> {
-    let mut path = PathBuf::from("inputs").join(year.to_string());
-    std::fs::create_dir_all(&path)?;
-    path.push(day.to_string());
+    let path = input_path(year, day);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
 
     if !path.exists() {
         let client = Client::new();
@@ -233,9 +328,7 @@ fn get_online_io_driver(year: u16, day: u8) -> anyhow::Result<Box<dyn IODriver>>
 }
 
 fn get_test_io_driver(year: u16, day: u8) -> anyhow::Result<Box<dyn IODriver>> {
-    let mut path = PathBuf::from("tests").join(year.to_string());
-    std::fs::create_dir_all(&path)?;
-    path.push(day.to_string());
+    let path = test_path(year, day);
     std::fs::create_dir_all(&path)?;
 
     let open_or_create_and_read = |p: &Path, create: bool| -> anyhow::Result<String> {
@@ -270,10 +363,10 @@ fn get_test_io_driver(year: u16, day: u8) -> anyhow::Result<Box<dyn IODriver>> {
             self.inputs.as_deref()
         }
 
-        fn validate(&self, part: Part, solution: &str) -> Option<bool> {
+        fn validate(&self, part: Part, solution: &PartResult) -> Option<bool> {
             Some(match part {
-                Part::One => solution == self.answer_1,
-                Part::Two => solution == self.answer_2,
+                Part::One => solution.matches(&self.answer_1),
+                Part::Two => solution.matches(&self.answer_2),
             })
         }
     }
@@ -310,11 +403,45 @@ enum Part {
 
 trait IODriver {
     fn get_inputs(&self) -> MaybeShared<&str>;
-    fn validate(&self, _part: Part, _solution: &str) -> Option<bool> {
+    fn validate(&self, _part: Part, _solution: &PartResult) -> Option<bool> {
         None
     }
 }
 
+fn result_to_json(result: &DayResult) -> serde_json::Value {
+    fn answer(part: &PartResult) -> serde_json::Value {
+        match part {
+            PartResult::Unresolved => serde_json::Value::Null,
+            PartResult::Integer(n) => i64::try_from(*n)
+                .map(Into::into)
+                .or_else(|_| u64::try_from(*n).map(Into::into))
+                .unwrap_or_else(|_| n.to_string().into()),
+            PartResult::Text(s) | PartResult::MultiLine(s) => s.as_str().into(),
+            PartResult::Ocr { text, .. } => text.as_str().into(),
+        }
+    }
+
+    let (duration_1, duration_2) = match result.stats {
+        MaybeShared::Shared(stats) => (stats, None),
+        MaybeShared::Separate(stats_1, stats_2) => (stats_1, Some(stats_2)),
+    };
+
+    serde_json::json!({
+        "year": result.year,
+        "day": result.day,
+        "label": result.label,
+        "part_1": {
+            "answer": answer(&result.part_1),
+            "validated": result.part_1_validated,
+        },
+        "part_2": {
+            "answer": answer(&result.part_2),
+            "validated": result.part_2_validated,
+        },
+        "duration_ns": (duration_1 + duration_2.unwrap_or_default()).as_nanos() as u64,
+    })
+}
+
 fn instrument<T>(f: impl FnOnce() -> T) -> (T, Duration) {
     let n = Instant::now();
     let res = f();
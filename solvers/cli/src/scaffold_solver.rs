@@ -15,9 +15,9 @@ const LIB_RS_TEMPLATE: &str = r#"use common::prelude::*;
 
 use chumsky::prelude::*;
 
-register_solver!(<year>, <day>, Solver);
 pub struct Solver;
 
+#[solver]
 impl DualDaySolver for Solver {
     fn solve_1(&self, input: &str) -> PartResult {
         // let input = parser().parse(input).unwrap();
@@ -54,12 +54,7 @@ pub(crate) fn scaffold_solver(year: u16, day: u8) -> anyhow::Result<()> {
     let src_dir = crate_root.join("src");
     std::fs::create_dir_all(&src_dir)?;
     let lib_rs = src_dir.join("lib.rs");
-    std::fs::File::create_new(&lib_rs)?.write_all(
-        LIB_RS_TEMPLATE
-            .replace("<year>", &year.to_string())
-            .replace("<day>", &day.to_string())
-            .as_bytes(),
-    )?;
+    std::fs::File::create_new(&lib_rs)?.write_all(LIB_RS_TEMPLATE.as_bytes())?;
 
     eprintln!(
         "Scaffolding done, now implement your solution: {}",
@@ -0,0 +1,56 @@
+use anyhow::Context;
+use common::PartResult;
+use reqwest::blocking::Client;
+
+use crate::{answer_path, Part};
+
+/// Submits `result` as the answer to `part`, refusing answers that cannot be
+/// right. A right answer is stored as the day's known answer.
+pub(crate) fn submit(year: u16, day: u8, part: Part, result: &PartResult) -> anyhow::Result<()> {
+    let answer = result.submission().map_err(anyhow::Error::msg)?;
+    let level = match part {
+        Part::One => "1",
+        Part::Two => "2",
+    };
+
+    let session_key = std::fs::read_to_string("session-key")
+        .with_context(|| "could not open `session-key` file")?;
+    let response = Client::new()
+        .post(format!("https://adventofcode.com/{year}/day/{day}/answer"))
+        .header("Cookie", format!("session={}", session_key.trim()))
+        .form(&[("level", level), ("answer", answer.as_str())])
+        .send()?
+        .error_for_status()
+        .with_context(|| format!("could not submit answer for year {year} day {day}"))?
+        .text()?;
+
+    let message = article_text(&response).unwrap_or(&response);
+    println!("{}", strip_tags(message));
+
+    if message.contains("That's the right answer") {
+        std::fs::write(answer_path(year, day, part), &answer)?;
+    }
+
+    Ok(())
+}
+
+fn article_text(html: &str) -> Option<&str> {
+    let start = html.find("<article")?;
+    let start = start + html[start..].find('>')? + 1;
+    let end = start + html[start..].find("</article>")?;
+    Some(&html[start..end])
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
@@ -0,0 +1,190 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span};
+use quote::{format_ident, quote};
+use syn::{
+    parse::Parser, parse_macro_input, punctuated::Punctuated, Expr, ExprLit, ItemImpl, Lit,
+    MetaNameValue, Token,
+};
+
+const METADATA_KEYS: [&str; 5] = ["title", "description", "tags", "complexity", "author"];
+
+/// Registers the solver implemented by the annotated `DualDaySolver` or
+/// `MonoDaySolver` impl, year and day being inferred from the crate name
+/// (`year2024_day16`) unless given as `year = 2024, day = 16`.
+///
+/// ```ignore
+/// #[solver(label = "petgraph", tags = [Grid, Graph])]
+/// impl MonoDaySolver for SolverPetgraph { ... }
+/// ```
+///
+/// Besides `label`, accepts the same metadata keys as `register_solver!`.
+///
+/// The reserved `all` label is rejected at compile time, as are two
+/// registrations of the same label in the same module. Duplicates across
+/// modules are only reported by `Solvers::new`.
+#[proc_macro_attribute]
+pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse(attr) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let item_impl = parse_macro_input!(item as ItemImpl);
+
+    match expand(args, &item_impl) {
+        Ok(registration) => quote! {
+            #item_impl
+            #registration
+        }
+        .into(),
+        Err(e) => {
+            let e = e.to_compile_error();
+            quote! {
+                #item_impl
+                #e
+            }
+            .into()
+        }
+    }
+}
+
+fn expand(
+    args: Punctuated<MetaNameValue, Token![,]>,
+    item_impl: &ItemImpl,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let Some((_, trait_path, _)) = &item_impl.trait_ else {
+        return Err(syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "#[solver] must be placed on a `DualDaySolver` or `MonoDaySolver` impl",
+        ));
+    };
+    match trait_path.segments.last() {
+        Some(s) if s.ident == "DualDaySolver" || s.ident == "MonoDaySolver" => {}
+        _ => {
+            return Err(syn::Error::new_spanned(
+                trait_path,
+                "#[solver] must be placed on a `DualDaySolver` or `MonoDaySolver` impl",
+            ))
+        }
+    }
+
+    let mut year = None;
+    let mut day = None;
+    let mut label = None;
+    let mut metadata = vec![];
+    for arg in args {
+        let Some(key) = arg.path.get_ident() else {
+            return Err(syn::Error::new_spanned(&arg.path, "expected an identifier"));
+        };
+
+        if key == "year" {
+            year = Some(int_literal(&arg.value)?);
+        } else if key == "day" {
+            day = Some(int_literal(&arg.value)?);
+        } else if key == "label" {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(l), ..
+            }) = &arg.value
+            else {
+                return Err(syn::Error::new_spanned(
+                    &arg.value,
+                    "label must be a string literal",
+                ));
+            };
+            if l.value() == "all" {
+                return Err(syn::Error::new_spanned(l, "`all` is a reserved label"));
+            }
+            label = Some(l.clone());
+        } else if METADATA_KEYS.iter().any(|k| key == k) {
+            let value = &arg.value;
+            metadata.push(quote! { #key = #value });
+        } else {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!(
+                    "unknown key `{key}`, expected `year`, `day`, `label` or one of: {}",
+                    METADATA_KEYS.join(", ")
+                ),
+            ));
+        }
+    }
+
+    let (year, day) = match (year, day) {
+        (Some(year), Some(day)) => (year, day),
+        (year, day) => {
+            let (inferred_year, inferred_day) = year_and_day_from_crate_name()?;
+            (year.unwrap_or(inferred_year), day.unwrap_or(inferred_day))
+        }
+    };
+
+    // Registering twice the same label in a module defines this constant
+    // twice, which is caught at compile time. Across modules, duplicates are
+    // still reported by `Solvers::new`.
+    let guard = format_ident!(
+        "__SOLVER_REGISTRATION_{year}_{day}_{}",
+        match &label {
+            Some(l) => format!("LABEL_{}", sanitize(&l.value())),
+            None => "DEFAULT".to_string(),
+        }
+    );
+
+    let self_ty = &item_impl.self_ty;
+    let year = Literal::u16_unsuffixed(year);
+    let day = Literal::u8_unsuffixed(day);
+    let label = label.map(|l| quote! { , #l });
+
+    Ok(quote! {
+        #[allow(dead_code, non_upper_case_globals)]
+        const #guard: () = ();
+
+        ::common::register_solver!(#year, #day, (#self_ty) #label #(, #metadata)*);
+    })
+}
+
+fn int_literal<N>(value: &Expr) -> syn::Result<N>
+where
+    N: std::str::FromStr,
+    N::Err: std::fmt::Display,
+{
+    match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(i), ..
+        }) => i.base10_parse(),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "expected an integer literal",
+        )),
+    }
+}
+
+fn year_and_day_from_crate_name() -> syn::Result<(u16, u8)> {
+    let name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let error = || {
+        syn::Error::new(
+            Span::call_site(),
+            format!("could not infer year and day from crate name `{name}`, expected `year<year>_day<day>`"),
+        )
+    };
+
+    let (year, day) = name
+        .strip_prefix("year")
+        .and_then(|s| s.split_once("_day"))
+        .ok_or_else(error)?;
+
+    Ok((
+        year.parse().map_err(|_| error())?,
+        day.parse().map_err(|_| error())?,
+    ))
+}
+
+fn sanitize(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_string()
+            } else {
+                format!("_{:x}_", c as u32)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,177 @@
+//! Cycle detection in the sequence of states `initial, step(initial), ...`.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// States from step `start` on repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// First step reaching the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Remembers every state, stepping exactly `start + length` times.
+pub fn find_cycle<S: Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    try_find_cycle(initial, |s| Some(step(s))).unwrap()
+}
+
+/// Like [`find_cycle`] for sequences that may end, when `step` returns
+/// `None`, in which case there is no cycle.
+pub fn try_find_cycle<S: Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        let next = step(&state)?;
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// Brent's algorithm, only keeping two states at a time.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Floyd's tortoise and hare, only keeping two states at a time.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// State after `n` steps, skipping whole cycles once one is found.
+pub fn nth_state<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(n));
+        }
+        seen.insert(state.clone(), i);
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finders_agree() {
+        // Some sequences enter their cycle late, values below 3 jump away.
+        for m in 1..40u64 {
+            for a in 0..m {
+                for start in 0..m {
+                    let step = |x: &u64| (x * x + a) % m + if *x < 3 { 50 } else { 0 };
+                    let cycle = find_cycle(start, step);
+                    assert_eq!(brent(start, step), cycle);
+                    assert_eq!(floyd(start, step), cycle);
+
+                    let mut state = start;
+                    for n in 0..200 {
+                        let mut equivalent = start;
+                        for _ in 0..cycle.equivalent_step(n) {
+                            equivalent = step(&equivalent);
+                        }
+                        assert_eq!(equivalent, state);
+                        state = step(&state);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fast_forward() {
+        assert_eq!(nth_state(0u64, |x| (x + 1) % 7, 1_000_000_000), 6);
+        assert_eq!(nth_state(0u64, |x| (x + 1).min(10), 1_000_000_000), 10);
+        assert_eq!(nth_state(3u64, |x| x + 1, 5), 8);
+    }
+
+    #[test]
+    fn sequences_without_cycle() {
+        assert_eq!(try_find_cycle(0, |x| (*x < 5).then_some(x + 1)), None);
+        assert_eq!(
+            try_find_cycle(0, |x| Some((x + 1) % 3)),
+            Some(Cycle {
+                start: 0,
+                length: 3
+            })
+        );
+    }
+}
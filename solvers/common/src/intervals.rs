@@ -0,0 +1,319 @@
+use std::ops::{Range, RangeInclusive};
+
+/// Set of integers, stored as sorted, disjoint and non adjacent ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<i64>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end.abs_diff(r.start)).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        *self = self.union(&IntervalSet::from(range));
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+        for r in &self.ranges {
+            let mut start = r.start;
+            while let Some(o) = others.peek() {
+                if o.end <= start {
+                    others.next();
+                    continue;
+                }
+                if o.start >= r.end {
+                    break;
+                }
+                if o.start > start {
+                    ranges.push(start..o.start);
+                }
+                start = o.end;
+                if o.end > r.end {
+                    break;
+                }
+                others.next();
+            }
+            if start < r.end {
+                ranges.push(start..r.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn is_disjoint(&self, other: &IntervalSet) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    pub fn is_subset(&self, other: &IntervalSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &IntervalSet) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Every value moved by `offset`.
+    pub fn shift(&self, offset: i64) -> IntervalSet {
+        IntervalSet {
+            ranges: self
+                .iter()
+                .map(|r| r.start + offset..r.end + offset)
+                .collect(),
+        }
+    }
+
+    /// Splits the set in its values inside and outside of `other`.
+    pub fn split(&self, other: &IntervalSet) -> (IntervalSet, IntervalSet) {
+        (self.intersection(other), self.difference(other))
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        [range].into_iter().collect()
+    }
+}
+
+/// Panics if the range ends at `i64::MAX`, sets being made of half-open
+/// ranges which cannot include it.
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        let end = range
+            .end()
+            .checked_add(1)
+            .expect("interval sets cannot include i64::MAX");
+        IntervalSet::from(*range.start()..end)
+    }
+}
+
+/// Empty ranges are dropped, overlapping or adjacent ones merged.
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut all = iter
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<_>>();
+        all.sort_by_key(|r| r.start);
+
+        let mut ranges: Vec<Range<i64>> = vec![];
+        for r in all {
+            match ranges.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => ranges.push(r),
+            }
+        }
+        IntervalSet { ranges }
+    }
+}
+
+impl std::ops::BitOr for &IntervalSet {
+    type Output = IntervalSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl std::ops::BitAnd for &IntervalSet {
+    type Output = IntervalSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl std::ops::Sub for &IntervalSet {
+    type Output = IntervalSet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+/// Function shifting values by an offset depending on the range they are
+/// in, leaving values outside of all ranges as is. When ranges overlap, the
+/// first one added wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiecewiseShift {
+    pieces: Vec<(Range<i64>, i64)>,
+}
+
+impl PiecewiseShift {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, source: Range<i64>, offset: i64) {
+        self.pieces.push((source, offset));
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(_, offset)| value + offset)
+    }
+
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        let mut remaining = set.clone();
+        let mut mapped = IntervalSet::new();
+        for (source, offset) in &self.pieces {
+            let (inside, outside) = remaining.split(&IntervalSet::from(source.clone()));
+            mapped = mapped.union(&inside.shift(*offset));
+            remaining = outside;
+        }
+        mapped.union(&remaining)
+    }
+}
+
+impl FromIterator<(Range<i64>, i64)> for PiecewiseShift {
+    fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+        PiecewiseShift {
+            pieces: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn random(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    fn random_set(seed: &mut u64) -> (IntervalSet, BTreeSet<i64>) {
+        let ranges = (0..random(seed) % 5)
+            .map(|_| {
+                let start = (random(seed) % 40) as i64 - 5;
+                start..start + (random(seed) % 8) as i64
+            })
+            .collect::<Vec<_>>();
+        let values = ranges.iter().flat_map(|r| r.clone()).collect();
+        (ranges.into_iter().collect(), values)
+    }
+
+    fn values(set: &IntervalSet) -> BTreeSet<i64> {
+        set.iter().flatten().collect()
+    }
+
+    #[test]
+    fn operations_match_brute_force() {
+        let mut seed = 0x853c_49e6_748f_ea9b;
+        for _ in 0..3000 {
+            let (a, va) = random_set(&mut seed);
+            let (b, vb) = random_set(&mut seed);
+
+            assert!(a.ranges().windows(2).all(|w| w[0].end < w[1].start));
+            assert!(a.ranges().iter().all(|r| !r.is_empty()));
+            assert_eq!(a.len() as usize, va.len());
+            assert_eq!(a.min(), va.first().copied());
+            assert_eq!(a.max(), va.last().copied());
+            for v in -6..50 {
+                assert_eq!(a.contains(v), va.contains(&v));
+            }
+
+            assert_eq!(values(&(&a | &b)), &va | &vb);
+            assert_eq!(values(&(&a & &b)), &va & &vb);
+            assert_eq!(values(&(&a - &b)), &va - &vb);
+            assert_eq!(a.is_subset(&b), va.is_subset(&vb));
+            assert_eq!(a.is_superset(&b), va.is_superset(&vb));
+            assert_eq!(a.is_disjoint(&b), va.is_disjoint(&vb));
+            assert_eq!(a.split(&b), (&a & &b, &a - &b));
+            assert_eq!(
+                values(&a.shift(-3)),
+                va.iter().map(|v| v - 3).collect::<BTreeSet<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn inclusive_ranges() {
+        assert_eq!(IntervalSet::from(2..=4), IntervalSet::from(2..5));
+        let (start, end) = (4, 3);
+        assert!(IntervalSet::from(start..=end).is_empty());
+        assert_eq!(IntervalSet::from(i64::MIN..=i64::MIN).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot include i64::MAX")]
+    fn inclusive_range_up_to_max() {
+        let _ = IntervalSet::from(0..=i64::MAX);
+    }
+
+    #[test]
+    fn piecewise_shift() {
+        let shift = [(0..10, 100), (5..15, -5)]
+            .into_iter()
+            .collect::<PiecewiseShift>();
+        assert_eq!(shift.apply(-1), -1);
+        assert_eq!(shift.apply(7), 107);
+        assert_eq!(shift.apply(12), 7);
+        assert_eq!(shift.apply(15), 15);
+
+        let set = IntervalSet::from(-2..20);
+        let expected = (-2..20).map(|v| shift.apply(v)).collect::<BTreeSet<_>>();
+        assert_eq!(values(&shift.apply_set(&set)), expected);
+    }
+}
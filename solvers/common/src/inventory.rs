@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 use crate::{DaySolver, DaySolverImpl};
 
@@ -10,32 +10,118 @@ pub struct RegisteredSolver {
     pub day: u8,
     pub constructor: DaySolverBuilder,
     pub label: Option<&'static str>,
+    pub metadata: SolverMetadata,
 }
 
 inventory::collect!(RegisteredSolver);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tag {
+    Grid,
+    Graph,
+    Dp,
+    Simulation,
+    Math,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 5] = [Tag::Grid, Tag::Graph, Tag::Dp, Tag::Simulation, Tag::Math];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Grid => "grid",
+            Tag::Graph => "graph",
+            Tag::Dp => "dp",
+            Tag::Simulation => "simulation",
+            Tag::Math => "math",
+        }
+    }
+}
+
+impl std::fmt::Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tag::ALL
+            .into_iter()
+            .find(|t| t.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown tag `{s}`"))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SolverMetadata {
+    pub title: Option<&'static str>,
+    pub description: Option<&'static str>,
+    pub tags: &'static [Tag],
+    pub complexity: Option<&'static str>,
+    pub author: Option<&'static str>,
+}
+
+impl SolverMetadata {
+    pub const EMPTY: Self = SolverMetadata {
+        title: None,
+        description: None,
+        tags: &[],
+        complexity: None,
+        author: None,
+    };
+}
+
+/// Registers a solver, optionally labeled and followed by metadata:
+///
+/// ```ignore
+/// register_solver!(2024, 16, SolverPetgraph, "petgraph", title = "Reindeer Maze", tags = [Grid, Graph]);
+/// ```
+///
+/// Accepted metadata keys are `title`, `description`, `tags`, `complexity`
+/// and `author`.
 #[macro_export]
 macro_rules! register_solver {
-    ($year:tt, $day:tt, $constructor:tt) => {
+    ($year:tt, $day:tt, $constructor:tt $(, $key:ident = $value:tt)* $(,)?) => {
         $crate::inventory::submit! {
-            $crate::inventory::RegisteredSolver { day: $day, year: $year, constructor: || ($constructor).to_day_solver_impl(), label: None }
+            $crate::inventory::RegisteredSolver { day: $day, year: $year, constructor: || ($constructor).to_day_solver_impl(), label: None, metadata: $crate::solver_metadata!($($key = $value),*) }
         }
     };
-    ($year:tt, $day:tt, $constructor:tt, $label:literal) => {
+    ($year:tt, $day:tt, $constructor:tt, $label:literal $(, $key:ident = $value:tt)* $(,)?) => {
         $crate::inventory::submit! {
-            $crate::inventory::RegisteredSolver { day: $day, year: $year, constructor: || ($constructor).to_day_solver_impl(), label: Some($label) }
+            $crate::inventory::RegisteredSolver { day: $day, year: $year, constructor: || ($constructor).to_day_solver_impl(), label: Some($label), metadata: $crate::solver_metadata!($($key = $value),*) }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! solver_metadata {
+    ($($key:ident = $value:tt),*) => {
+        $crate::inventory::SolverMetadata {
+            $($key: $crate::solver_metadata!(@value $key $value),)*
+            ..$crate::inventory::SolverMetadata::EMPTY
         }
     };
+    (@value tags [$($tag:ident),* $(,)?]) => {
+        &[$($crate::inventory::Tag::$tag),*]
+    };
+    (@value $key:ident $value:literal) => {
+        Some($value)
+    };
 }
 
 pub use register_solver;
+pub use solver_metadata;
 
 type YearSolverMap = BTreeMap<u16, DaySolverMap>;
 
-#[derive(Debug, Default)]
+#[derive(Default)]
 struct DaySolvers {
-    default: Option<DaySolverBuilder>,
-    labeled: HashMap<&'static str, DaySolverBuilder>,
+    default: Option<&'static RegisteredSolver>,
+    labeled: BTreeMap<&'static str, &'static RegisteredSolver>,
 }
 
 type DaySolverMap = BTreeMap<u8, DaySolvers>;
@@ -67,7 +153,7 @@ impl Solvers {
                         ));
                     }
 
-                    if day.labeled.insert(label, solver.constructor).is_some() {
+                    if day.labeled.insert(label, solver).is_some() {
                         return Err(format!(
                             "Attempted to register two solvers for year {} day {} with label `{}`",
                             solver.year, solver.day, label
@@ -82,7 +168,7 @@ impl Solvers {
                         ));
                     }
 
-                    day.default = Some(solver.constructor);
+                    day.default = Some(solver);
                 }
             }
 
@@ -110,7 +196,7 @@ impl Solvers {
                         match label_query {
                             LabelQuery::DefaultOnly => {
                                 if let Some(default) = solvers.default {
-                                    vec![(None, default())]
+                                    vec![(None, default)]
                                 } else {
                                     vec![]
                                 }
@@ -119,23 +205,24 @@ impl Solvers {
                                 .labeled
                                 .iter()
                                 .filter(|(label, _)| labels.contains(label))
-                                .map(|(label, c)| (Some(*label), c()))
+                                .map(|(label, s)| (Some(*label), *s))
                                 .collect::<Vec<_>>(),
 
                             LabelQuery::All => solvers
                                 .default
                                 .as_ref()
-                                .map(|c| (None, c()))
+                                .map(|s| (None, *s))
                                 .into_iter()
-                                .chain(solvers.labeled.iter().map(|(label, c)| (Some(*label), c())))
+                                .chain(solvers.labeled.iter().map(|(label, s)| (Some(*label), *s)))
                                 .collect::<Vec<_>>(),
                         }
                         .into_iter()
-                        .map(move |(label, implementation)| DaySolver {
+                        .map(move |(label, solver)| DaySolver {
                             day,
                             year,
                             label,
-                            implementation,
+                            metadata: &solver.metadata,
+                            implementation: (solver.constructor)(),
                         })
                     })
             })
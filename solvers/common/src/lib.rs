@@ -1,30 +1,164 @@
 use std::fmt::Display;
 
+pub mod cycle;
+pub mod debug;
+pub mod intervals;
 pub mod inventory;
 pub mod macros;
 pub mod map;
 pub mod math_helper;
+pub mod memo;
+pub mod ocr;
 pub mod prelude;
 
-#[derive(Default)]
-pub struct PartResult(Option<Box<dyn Display>>);
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PartResult {
+    #[default]
+    Unresolved,
+    Integer(i128),
+    Text(String),
+    MultiLine(String),
+    /// Letters drawn on screen, decoded with [`ocr`].
+    Ocr {
+        text: String,
+        image: String,
+    },
+}
 
 impl Display for PartResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match &self.0 {
-            Some(r) => write!(f, "{}", r),
-            None => write!(f, "Unresolved"),
+        match self {
+            PartResult::Unresolved => write!(f, "Unresolved"),
+            PartResult::Integer(n) => write!(f, "{}", n),
+            PartResult::Text(s) | PartResult::MultiLine(s) => write!(f, "{}", s),
+            PartResult::Ocr { text, image } => write!(f, "{}\n{}", text, image),
         }
     }
 }
 
 impl PartResult {
-    pub fn new(val: impl Display) -> Self {
-        Self(Some(Box::new(val.to_string())))
+    pub fn new(val: impl Into<PartResult>) -> Self {
+        val.into()
+    }
+
+    /// Builds a textual result from anything that can be displayed.
+    pub fn from_display(val: impl Display) -> Self {
+        val.to_string().into()
+    }
+
+    /// Builds a result from letters drawn with `#` or `█`, keeping the image
+    /// for display. If the letters cannot be decoded, the image is kept as is.
+    pub fn ocr(image: impl Display) -> Self {
+        let image = image.to_string();
+        match ocr::recognize_str(&image) {
+            Ok(text) => PartResult::Ocr { text, image },
+            Err(_) => PartResult::MultiLine(image),
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        !matches!(self, PartResult::Unresolved)
     }
 
     pub fn resolved(&self) -> Option<String> {
-        self.0.as_ref().map(|val| val.to_string())
+        self.is_resolved().then(|| self.to_string())
+    }
+
+    /// Checks the result against an expected answer, numbers are compared by
+    /// value and surrounding whitespace is ignored.
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            PartResult::Unresolved => false,
+            PartResult::Integer(n) => expected.trim().parse::<i128>() == Ok(*n),
+            PartResult::Text(s) | PartResult::Ocr { text: s, .. } => s.trim() == expected.trim(),
+            PartResult::MultiLine(s) => {
+                fn lines(s: &str) -> impl Iterator<Item = &str> {
+                    s.trim_matches('\n').lines().map(str::trim_end)
+                }
+                lines(s).eq(lines(expected))
+            }
+        }
+    }
+
+    /// Returns the answer as it should be submitted to AoC, rejecting values
+    /// that cannot possibly be right.
+    pub fn submission(&self) -> Result<String, String> {
+        match self {
+            PartResult::Unresolved => Err("Answer is unresolved".to_string()),
+            PartResult::Integer(n) if *n < 0 => Err(format!("Answer `{n}` is negative")),
+            PartResult::Integer(n) => Ok(n.to_string()),
+            PartResult::Text(s) if s.trim().is_empty() => Err("Answer is empty".to_string()),
+            PartResult::Text(s) if s.trim().contains(char::is_whitespace) => {
+                Err(format!("Answer `{}` contains whitespace", s.trim()))
+            }
+            PartResult::Text(s) => Ok(s.trim().to_string()),
+            PartResult::Ocr { text, .. } => Ok(text.clone()),
+            PartResult::MultiLine(_) => Err("Answer spans multiple lines".to_string()),
+        }
+    }
+}
+
+macro_rules! impl_part_result_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for PartResult {
+                fn from(val: $t) -> Self {
+                    PartResult::Integer(val as i128)
+                }
+            }
+
+            impl From<&$t> for PartResult {
+                fn from(val: &$t) -> Self {
+                    PartResult::Integer(*val as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_part_result_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+/// Values above `i128::MAX` are kept as text.
+impl From<u128> for PartResult {
+    fn from(val: u128) -> Self {
+        match i128::try_from(val) {
+            Ok(n) => PartResult::Integer(n),
+            Err(_) => PartResult::Text(val.to_string()),
+        }
+    }
+}
+
+impl From<&u128> for PartResult {
+    fn from(val: &u128) -> Self {
+        (*val).into()
+    }
+}
+
+impl From<String> for PartResult {
+    fn from(val: String) -> Self {
+        if val.trim().contains('\n') {
+            PartResult::MultiLine(val)
+        } else {
+            PartResult::Text(val)
+        }
+    }
+}
+
+impl From<&str> for PartResult {
+    fn from(val: &str) -> Self {
+        val.to_string().into()
+    }
+}
+
+impl From<&String> for PartResult {
+    fn from(val: &String) -> Self {
+        val.clone().into()
+    }
+}
+
+impl From<char> for PartResult {
+    fn from(val: char) -> Self {
+        PartResult::Text(val.to_string())
     }
 }
 
@@ -72,6 +206,7 @@ pub struct DaySolver {
     pub year: u16,
     pub day: u8,
     pub label: Option<&'static str>,
+    pub metadata: &'static inventory::SolverMetadata,
     pub implementation: DaySolverImpl,
 }
 
@@ -87,3 +222,89 @@ impl MonoDaySolver for UnimplementedSolver {
         (PartResult::default(), PartResult::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_values() {
+        assert_eq!(PartResult::new(42u8), PartResult::Integer(42));
+        assert_eq!(PartResult::from(&-7i64), PartResult::Integer(-7));
+        assert_eq!(
+            PartResult::new(usize::MAX),
+            PartResult::Integer(usize::MAX as i128)
+        );
+        assert_eq!(PartResult::new(i128::MIN), PartResult::Integer(i128::MIN));
+        assert_eq!(PartResult::new(1u128 << 100), PartResult::Integer(1 << 100));
+        assert_eq!(
+            PartResult::new(u128::MAX),
+            PartResult::Text(u128::MAX.to_string())
+        );
+        assert_eq!(PartResult::new('x'), PartResult::Text("x".to_string()));
+        assert_eq!(PartResult::new("abc"), PartResult::Text("abc".to_string()));
+        assert_eq!(
+            PartResult::from(&"a b\n".to_string()),
+            PartResult::Text("a b\n".to_string())
+        );
+        assert_eq!(
+            PartResult::new("#.\n.#"),
+            PartResult::MultiLine("#.\n.#".to_string())
+        );
+        assert_eq!(
+            PartResult::from_display(1.5),
+            PartResult::Text("1.5".to_string())
+        );
+        assert_eq!(PartResult::default(), PartResult::Unresolved);
+    }
+
+    #[test]
+    fn matches() {
+        assert!(PartResult::new(42).matches("42"));
+        assert!(PartResult::new(42).matches(" 0042\n"));
+        assert!(PartResult::new(42).matches("+42"));
+        assert!(PartResult::new(-42).matches("-42"));
+        assert!(!PartResult::new(42).matches("42.0"));
+        assert!(!PartResult::new(42).matches("4 2"));
+        assert!(!PartResult::new(42).matches(""));
+
+        // The same number written as text only matches its exact writing.
+        assert!(PartResult::new("42").matches("42\n"));
+        assert!(!PartResult::new("42").matches("042"));
+        assert!(PartResult::new(u128::MAX).matches(&u128::MAX.to_string()));
+
+        assert!(PartResult::new("abc").matches("  abc\n"));
+        assert!(!PartResult::new("abc").matches("ABC"));
+        assert!(PartResult::new("#.\n.#").matches("\n#.  \n.#\n"));
+        assert!(!PartResult::new("#.\n.#").matches("#.\n#."));
+        assert!(!PartResult::Unresolved.matches(""));
+        assert!(!PartResult::Unresolved.matches("Unresolved"));
+    }
+
+    #[test]
+    fn submission() {
+        assert_eq!(PartResult::new(0).submission(), Ok("0".to_string()));
+        assert_eq!(PartResult::new(123).submission(), Ok("123".to_string()));
+        assert!(PartResult::new(-1).submission().is_err());
+        assert_eq!(
+            PartResult::new(u128::MAX).submission(),
+            Ok(u128::MAX.to_string())
+        );
+        assert_eq!(
+            PartResult::new(" 1,2,3\n").submission(),
+            Ok("1,2,3".to_string())
+        );
+        assert!(PartResult::new("  ").submission().is_err());
+        assert!(PartResult::new("a b").submission().is_err());
+        assert!(PartResult::new("#.\n.#").submission().is_err());
+        assert!(PartResult::Unresolved.submission().is_err());
+        assert_eq!(
+            PartResult::Ocr {
+                text: "AB".to_string(),
+                image: "...".to_string()
+            }
+            .submission(),
+            Ok("AB".to_string())
+        );
+    }
+}
@@ -1,25 +1,160 @@
-#[derive(Debug, Clone)]
-pub struct Map<T>(Vec<Vec<T>>);
+mod direction;
+pub mod hex;
+mod parse;
+mod polygon;
+pub mod region;
+pub mod render;
+pub mod search;
+mod sparse;
+mod vec_n;
+mod view;
+mod wrapping;
+
+pub use direction::*;
+pub use parse::*;
+pub use polygon::*;
+pub use sparse::*;
+pub use vec_n::*;
+pub use view::*;
+pub use wrapping::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
 
 impl<T> Map<T> {
+    /// Builds a map from its rows, panicking if they are not all of the same
+    /// length.
     pub fn new(map: Vec<Vec<T>>) -> Self {
-        Map(map)
+        Self::try_new(map).unwrap_or_else(|e| panic!("{e}"))
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
-        self.0.iter().enumerate().flat_map(|(y, row)| {
-            row.iter().enumerate().map(move |(x, val)| {
-                (
-                    Vec2 {
-                        x: x as i64,
-                        y: y as i64,
-                    },
-                    val,
-                )
-            })
+    pub fn try_new(map: Vec<Vec<T>>) -> Result<Self, String> {
+        let height = map.len();
+        let width = map.first().map(|r| r.len()).unwrap_or(0);
+
+        if let Some((y, row)) = map.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(format!(
+                "Ragged map: row {y} has {} cells, expected {width}",
+                row.len()
+            ));
+        }
+
+        Ok(Map {
+            data: map.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    pub fn from_vec(width: usize, data: Vec<T>) -> Result<Self, String> {
+        if !data.len().is_multiple_of(width) {
+            return Err(format!(
+                "Cannot build a map of width {width} from {} cells",
+                data.len()
+            ));
+        }
+
+        Ok(Map {
+            height: data.len().checked_div(width).unwrap_or(0),
+            data,
+            width,
         })
     }
 
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Map {
+            data: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> Vec2 {
+        vec2(self.width as i64, self.height as i64)
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    /// Index of `pos` in the underlying buffer.
+    pub fn index_of(&self, pos: Vec2) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Position of the cell at `index` in the underlying buffer.
+    pub fn pos_of(&self, index: usize) -> Vec2 {
+        vec2((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(
+            x < self.width,
+            "column {x} out of map of width {}",
+            self.width
+        );
+        (0..self.height).map(move |y| &self.data[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, val)| (self.pos_of(i), val))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vec2, &mut T)> {
+        let width = self.width;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, val)| (vec2((i % width) as i64, (i / width) as i64), val))
+    }
+
     pub fn iter_from_point(
         &self,
         start: Vec2,
@@ -36,8 +171,7 @@ impl<T> Map<T> {
     }
 
     pub fn eight_adjacent_iter(&self, pos: Vec2) -> impl Iterator<Item = &T> {
-        self.eight_adjacent_pos_iter(pos)
-            .map(|pos| &self.0[pos.y as usize][pos.x as usize])
+        self.eight_adjacent_pos_iter(pos).map(|pos| &self[pos])
     }
 
     pub fn eight_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
@@ -58,44 +192,29 @@ impl<T> Map<T> {
             vec2(x + 1, y + 1),
         ]
         .into_iter()
-        .filter(|&pos| {
-            pos.x >= 0
-                && pos.x < self.0[0].len() as i64
-                && pos.y >= 0
-                && pos.y < self.0.len() as i64
-        })
+        .filter(|&pos| self.contains(pos))
     }
 
     pub fn four_adjacent_iter(&self, pos: Vec2) -> impl Iterator<Item = &T> {
-        self.four_adjacent_pos_iter(pos)
-            .map(|pos| &self.0[pos.y as usize][pos.x as usize])
+        self.four_adjacent_pos_iter(pos).map(|pos| &self[pos])
     }
 
     pub fn four_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
-        pos.four_adjacent_iter().filter(|&pos| {
-            pos.x >= 0
-                && pos.x < self.0[0].len() as i64
-                && pos.y >= 0
-                && pos.y < self.0.len() as i64
-        })
+        pos.four_adjacent_iter().filter(|&pos| self.contains(pos))
     }
 
     pub fn get(&self, pos: Vec2) -> Option<&T> {
-        self.0.get(pos.y as usize)?.get(pos.x as usize)
+        self.index_of(pos).map(|i| &self.data[i])
     }
 
     pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
-        self.0.get_mut(pos.y as usize)?.get_mut(pos.x as usize)
-    }
-
-    pub fn inner(&self) -> &Vec<Vec<T>> {
-        &self.0
+        self.index_of(pos).map(|i| &mut self.data[i])
     }
 }
 
 impl<T: std::fmt::Display> std::fmt::Display for Map<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for l in &self.0 {
+        for l in self.rows() {
             for c in l {
                 write!(f, "{}", c)?;
             }
@@ -109,13 +228,19 @@ impl<T> std::ops::Index<Vec2> for Map<T> {
     type Output = T;
 
     fn index(&self, pos: Vec2) -> &Self::Output {
-        &self.0[pos.y as usize][pos.x as usize]
+        match self.index_of(pos) {
+            Some(i) => &self.data[i],
+            None => panic!("{pos:?} out of map of size {:?}", self.size()),
+        }
     }
 }
 
 impl<T> std::ops::IndexMut<Vec2> for Map<T> {
     fn index_mut(&mut self, pos: Vec2) -> &mut Self::Output {
-        &mut self.0[pos.y as usize][pos.x as usize]
+        match self.index_of(pos) {
+            Some(i) => &mut self.data[i],
+            None => panic!("{pos:?} out of map of size {:?}", self.size()),
+        }
     }
 }
 
@@ -155,14 +280,6 @@ impl Vec2 {
         ]
     }
 
-    pub fn rotate(self, angle: f32) -> Self {
-        let x = self.x as f32;
-        let y = self.y as f32;
-        let x_rotated = (x * angle.cos()) - (y * angle.sin());
-        let y_rotated = (y * angle.cos()) + (x * angle.sin());
-        vec2(x_rotated as i64, y_rotated as i64)
-    }
-
     pub fn square_norm(&self) -> i64 {
         self.x * self.x + self.y * self.y
     }
@@ -293,3 +410,109 @@ impl std::iter::Sum for Vec2 {
         iter.fold(Vec2::default(), |acc, v| acc + v)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Map<u8> {
+        Map::new(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert_eq!(
+            Map::try_new(vec![vec![1, 2], vec![3], vec![4, 5]]),
+            Err("Ragged map: row 1 has 1 cells, expected 2".to_string())
+        );
+        assert!(Map::try_new(vec![vec![], vec![1]]).is_err());
+        assert!(Map::from_vec(2, vec![1, 2, 3]).is_err());
+        assert!(Map::from_vec(0, vec![1]).is_err());
+        assert_eq!(Map::from_vec(3, vec![1, 2, 3, 4, 5, 6]), Ok(sample()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ragged map: row 2 has 3 cells, expected 2")]
+    fn new_panics_on_ragged_rows() {
+        Map::new(vec![vec![1, 2], vec![3, 4], vec![5, 6, 7]]);
+    }
+
+    #[test]
+    fn empty_maps() {
+        let empties = [
+            Map::<u8>::new(vec![]),
+            Map::from_vec(0, vec![]).unwrap(),
+            Map::from_vec(3, vec![]).unwrap(),
+            Map::filled(0, 0, 0),
+        ];
+        for map in empties {
+            assert_eq!(map.height(), 0);
+            assert_eq!(map.rows().len(), 0);
+            assert_eq!(map.iter().count(), 0);
+            assert!(!map.contains(vec2(0, 0)));
+            assert_eq!(map.get(vec2(0, 0)), None);
+            assert_eq!(map.index_of(vec2(0, 0)), None);
+            assert_eq!(map.to_string(), "");
+        }
+
+        // Rows without cells still count in the height.
+        let map = Map::<u8>::new(vec![vec![], vec![]]);
+        assert_eq!(map.size(), vec2(0, 2));
+        assert_eq!(map.rows().collect::<Vec<_>>(), vec![&[] as &[u8], &[]]);
+        assert_eq!(map.iter().count(), 0);
+        assert_eq!(map.get(vec2(0, 1)), None);
+        assert_eq!(map.four_adjacent_pos_iter(vec2(0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn index_and_position_round_trip() {
+        let map = Map::filled(7, 5, ());
+        for (i, _) in map.as_slice().iter().enumerate() {
+            assert_eq!(map.index_of(map.pos_of(i)), Some(i));
+        }
+        for y in -1..=5 {
+            for x in -1..=7 {
+                let pos = vec2(x, y);
+                match map.index_of(pos) {
+                    Some(i) => assert_eq!(map.pos_of(i), pos),
+                    None => assert!(!(0..7).contains(&x) || !(0..5).contains(&y)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut map = sample();
+        assert_eq!(map.size(), vec2(3, 2));
+        assert_eq!(map.row(1), &[4, 5, 6]);
+        assert_eq!(map.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(
+            map.columns()
+                .rev()
+                .map(|c| c.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![3, 6], vec![2, 5], vec![1, 4]]
+        );
+        assert_eq!(map[vec2(2, 0)], 3);
+        assert_eq!(map.get(vec2(3, 0)), None);
+        assert_eq!(map.get(vec2(-1, 1)), None);
+
+        map.row_mut(0)[1] = 9;
+        map[vec2(0, 1)] = 8;
+        assert_eq!(map.as_slice(), &[1, 9, 3, 8, 5, 6]);
+        assert_eq!(map.to_string(), "193\n856\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of map of width 3")]
+    fn column_out_of_map() {
+        let _ = sample().column(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_map() {
+        sample().row(2);
+    }
+}
@@ -0,0 +1,324 @@
+use super::{vec2, FromGridChar, Vec2};
+
+/// One of the four orthogonal directions, north being up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    #[default]
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise, starting north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    /// Parses `^v<>`, `NSEW` and `UDLR`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(Direction::North),
+            '>' | 'E' | 'R' => Some(Direction::East),
+            'v' | 'S' | 'D' => Some(Direction::South),
+            '<' | 'W' | 'L' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+impl FromGridChar for Direction {
+    fn from_grid_char(c: char) -> Option<Self> {
+        Direction::from_char(c)
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::North => Vec2::NORTH,
+            Direction::East => Vec2::EAST,
+            Direction::South => Vec2::SOUTH,
+            Direction::West => Vec2::WEST,
+        }
+    }
+}
+
+impl TryFrom<Vec2> for Direction {
+    type Error = String;
+
+    fn try_from(v: Vec2) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .into_iter()
+            .find(|d| Vec2::from(*d) == v)
+            .ok_or_else(|| format!("{v:?} is not an orthogonal unit vector"))
+    }
+}
+
+/// One of the eight directions, diagonals included.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise, starting north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    fn turn(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns 90° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn(6)
+    }
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    pub fn turn_left_45(self) -> Self {
+        self.turn(7)
+    }
+
+    pub fn turn_right_45(self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        (self as usize) % 2 == 1
+    }
+
+    /// Parses what [`Direction::from_char`] does, and the arrows `↑↗→↘↓↙←↖`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '↑' => Some(Direction8::North),
+            '↗' => Some(Direction8::NorthEast),
+            '→' => Some(Direction8::East),
+            '↘' => Some(Direction8::SouthEast),
+            '↓' => Some(Direction8::South),
+            '↙' => Some(Direction8::SouthWest),
+            '←' => Some(Direction8::West),
+            '↖' => Some(Direction8::NorthWest),
+            c => Direction::from_char(c).map(Direction8::from),
+        }
+    }
+}
+
+impl FromGridChar for Direction8 {
+    fn from_grid_char(c: char) -> Option<Self> {
+        Direction8::from_char(c)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Direction8::ALL[d as usize * 2]
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = String;
+
+    fn try_from(d: Direction8) -> Result<Self, Self::Error> {
+        if d.is_diagonal() {
+            Err(format!("{d:?} is not an orthogonal direction"))
+        } else {
+            Ok(Direction::ALL[d as usize / 2])
+        }
+    }
+}
+
+impl From<Direction8> for Vec2 {
+    fn from(d: Direction8) -> Self {
+        match d {
+            Direction8::North => Vec2::NORTH,
+            Direction8::NorthEast => Vec2::NORTH_EAST,
+            Direction8::East => Vec2::EAST,
+            Direction8::SouthEast => Vec2::SOUTH_EAST,
+            Direction8::South => Vec2::SOUTH,
+            Direction8::SouthWest => Vec2::SOUTH_WEST,
+            Direction8::West => Vec2::WEST,
+            Direction8::NorthWest => Vec2::NORTH_WEST,
+        }
+    }
+}
+
+impl TryFrom<Vec2> for Direction8 {
+    type Error = String;
+
+    fn try_from(v: Vec2) -> Result<Self, Self::Error> {
+        Direction8::ALL
+            .into_iter()
+            .find(|d| Vec2::from(*d) == v)
+            .ok_or_else(|| format!("{v:?} is not a unit vector"))
+    }
+}
+
+impl std::ops::Add<Direction> for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + Vec2::from(rhs)
+    }
+}
+
+impl std::ops::Add<Direction8> for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        self + Vec2::from(rhs)
+    }
+}
+
+impl Vec2 {
+    /// Exact 90° counterclockwise rotation, y pointing down.
+    pub fn rotate_left(self) -> Self {
+        vec2(self.y, -self.x)
+    }
+
+    /// Exact 90° clockwise rotation, y pointing down.
+    pub fn rotate_right(self) -> Self {
+        vec2(-self.y, self.x)
+    }
+
+    pub fn rotate_180(self) -> Self {
+        vec2(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_directions() {
+        for (alphabet, expected) in [
+            ("^>v<", Direction::ALL),
+            ("NESW", Direction::ALL),
+            ("URDL", Direction::ALL),
+        ] {
+            let parsed = alphabet.chars().map(Direction::from_char);
+            assert!(parsed.eq(expected.map(Some)), "{alphabet}");
+        }
+        for c in ['n', 'u', 'V', 'x', ' ', '.', '#', '1', '↑'] {
+            assert_eq!(Direction::from_char(c), None, "{c:?}");
+        }
+        for d in Direction::ALL {
+            assert_eq!(Direction::from_char(d.to_arrow()), Some(d));
+        }
+
+        assert!("↑↗→↘↓↙←↖"
+            .chars()
+            .map(Direction8::from_char)
+            .eq(Direction8::ALL.map(Some)));
+        assert_eq!(Direction8::from_char('<'), Some(Direction8::West));
+        assert_eq!(Direction8::from_char('U'), Some(Direction8::North));
+        for c in ['x', ' ', '.', '⇒'] {
+            assert_eq!(Direction8::from_char(c), None, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(Vec2::from(d.turn_right()), Vec2::from(d).rotate_right());
+            assert_eq!(Vec2::from(d.turn_left()), Vec2::from(d).rotate_left());
+            assert_eq!(Vec2::from(d.opposite()), Vec2::from(d).rotate_180());
+            assert_eq!(Direction::try_from(Vec2::from(d)), Ok(d));
+            assert_eq!(Direction::try_from(Direction8::from(d)), Ok(d));
+        }
+        for d in Direction8::ALL {
+            assert_eq!(d.turn_right_45().turn_right_45(), d.turn_right());
+            assert_eq!(d.turn_left_45().turn_right_45(), d);
+            assert_eq!(Vec2::from(d.turn_right()), Vec2::from(d).rotate_right());
+            assert_eq!(Vec2::from(d.turn_left()), Vec2::from(d).rotate_left());
+            assert_eq!(Direction8::try_from(Vec2::from(d)), Ok(d));
+            assert_eq!(Direction::try_from(d).is_err(), d.is_diagonal());
+        }
+        assert!(Direction::try_from(vec2(2, 0)).is_err());
+        assert!(Direction8::try_from(vec2(0, 0)).is_err());
+    }
+
+    #[test]
+    fn rotations_with_y_down() {
+        // Clockwise on screen: east goes south, south goes west.
+        assert_eq!(Vec2::EAST.rotate_right(), Vec2::SOUTH);
+        assert_eq!(Vec2::SOUTH.rotate_right(), Vec2::WEST);
+        assert_eq!(Vec2::NORTH.rotate_left(), Vec2::WEST);
+        assert_eq!(Vec2::NORTH_EAST.rotate_right(), Vec2::SOUTH_EAST);
+        assert_eq!(vec2(3, 1).rotate_right(), vec2(-1, 3));
+        assert_eq!(vec2(3, 1).rotate_left(), vec2(1, -3));
+        assert_eq!(vec2(3, 1).rotate_180(), vec2(-3, -1));
+
+        let v = vec2(5, -2);
+        assert_eq!(v.rotate_left().rotate_right(), v);
+        assert_eq!(v.rotate_right().rotate_right(), v.rotate_180());
+        assert_eq!(
+            v.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            v
+        );
+        assert_eq!(v.rotate_right().square_norm(), v.square_norm());
+    }
+}
@@ -0,0 +1,426 @@
+//! Hexagonal grids with flat-topped cells, using axial coordinates: `q`
+//! grows going south east and `r` going south.
+
+use std::{collections::HashMap, str::FromStr};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+pub fn hex(q: impl Into<i64>, r: impl Into<i64>) -> Hex {
+    Hex {
+        q: q.into(),
+        r: r.into(),
+    }
+}
+
+impl Hex {
+    /// Third cube coordinate, such that `q + r + s == 0`.
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "({q}, {r}, {s}) is not a cube coordinate");
+        hex(q, r)
+    }
+
+    /// Number of steps from the origin.
+    pub fn norm(&self) -> i64 {
+        (self.q.abs() + self.r.abs() + self.s().abs()) / 2
+    }
+
+    pub fn distance(&self, other: Hex) -> i64 {
+        (*self - other).norm()
+    }
+
+    pub fn six_adjacent_iter(&self) -> impl Iterator<Item = Hex> {
+        let pos = *self;
+        HexDirection::ALL.into_iter().map(move |d| pos + d)
+    }
+
+    /// Cells exactly `radius` steps away, going clockwise from the north.
+    pub fn ring(&self, radius: u32) -> impl Iterator<Item = Hex> {
+        let start = *self + Hex::from(HexDirection::North) * radius as i64;
+        // Walking clockwise from the north corner starts south east.
+        let steps = HexDirection::ALL
+            .into_iter()
+            .cycle()
+            .skip(2)
+            .take(6)
+            .flat_map(move |d| std::iter::repeat_n(d, radius as usize));
+        let center = (radius == 0).then_some(start);
+        center.into_iter().chain(steps.scan(start, |pos, d| {
+            let current = *pos;
+            *pos = *pos + d;
+            Some(current)
+        }))
+    }
+
+    /// Cells at most `radius` steps away, the center first.
+    pub fn spiral(&self, radius: u32) -> impl Iterator<Item = Hex> {
+        let center = *self;
+        (0..=radius).flat_map(move |r| center.ring(r))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// Clockwise, starting north.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::North,
+        HexDirection::NorthEast,
+        HexDirection::SouthEast,
+        HexDirection::South,
+        HexDirection::SouthWest,
+        HexDirection::NorthWest,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 5) % 6]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 6]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 3) % 6]
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(HexDirection::North),
+            "ne" => Ok(HexDirection::NorthEast),
+            "se" => Ok(HexDirection::SouthEast),
+            "s" => Ok(HexDirection::South),
+            "sw" => Ok(HexDirection::SouthWest),
+            "nw" => Ok(HexDirection::NorthWest),
+            other => Err(format!("Unknown hex direction `{other}`")),
+        }
+    }
+}
+
+impl std::fmt::Display for HexDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            HexDirection::North => "n",
+            HexDirection::NorthEast => "ne",
+            HexDirection::SouthEast => "se",
+            HexDirection::South => "s",
+            HexDirection::SouthWest => "sw",
+            HexDirection::NorthWest => "nw",
+        })
+    }
+}
+
+/// Parses a comma separated path such as `ne,ne,s,s`.
+pub fn parse_path(input: &str) -> Result<Vec<HexDirection>, String> {
+    input.trim().split(',').map(|d| d.trim().parse()).collect()
+}
+
+impl From<HexDirection> for Hex {
+    fn from(d: HexDirection) -> Self {
+        match d {
+            HexDirection::North => hex(0, -1),
+            HexDirection::NorthEast => hex(1, -1),
+            HexDirection::SouthEast => hex(1, 0),
+            HexDirection::South => hex(0, 1),
+            HexDirection::SouthWest => hex(-1, 1),
+            HexDirection::NorthWest => hex(-1, 0),
+        }
+    }
+}
+
+impl std::ops::Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex {
+            q: self.q + rhs.q,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl std::ops::Add<HexDirection> for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: HexDirection) -> Self::Output {
+        self + Hex::from(rhs)
+    }
+}
+
+impl std::ops::Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex {
+            q: self.q - rhs.q,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+impl std::ops::Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Hex {
+            q: self.q * rhs,
+            r: self.r * rhs,
+        }
+    }
+}
+
+impl std::ops::Mul<Hex> for i64 {
+    type Output = Hex;
+
+    fn mul(self, rhs: Hex) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl std::ops::Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Self::Output {
+        Hex {
+            q: -self.q,
+            r: -self.r,
+        }
+    }
+}
+
+impl std::iter::Sum for Hex {
+    fn sum<I: Iterator<Item = Hex>>(iter: I) -> Self {
+        iter.fold(Hex::default(), |acc, v| acc + v)
+    }
+}
+
+/// Hexagonal grid storing only its occupied cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexMap<T> {
+    cells: HashMap<Hex, T>,
+}
+
+impl<T> Default for HexMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> HexMap<T> {
+    pub fn new() -> Self {
+        HexMap {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Hex) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Hex, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Hex) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn get(&self, pos: Hex) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Hex) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Hex, &mut T)> {
+        self.cells.iter_mut().map(|(p, v)| (*p, v))
+    }
+
+    pub fn six_adjacent_iter(&self, pos: Hex) -> impl Iterator<Item = &T> {
+        self.six_adjacent_pos_iter(pos).map(|pos| &self[pos])
+    }
+
+    /// Occupied cells among the six adjacent ones.
+    pub fn six_adjacent_pos_iter(&self, pos: Hex) -> impl Iterator<Item = Hex> + '_ {
+        pos.six_adjacent_iter().filter(|&pos| self.contains(pos))
+    }
+}
+
+impl<T> FromIterator<(Hex, T)> for HexMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Hex, T)>>(iter: I) -> Self {
+        HexMap {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Hex, T)> for HexMap<T> {
+    fn extend<I: IntoIterator<Item = (Hex, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter);
+    }
+}
+
+impl<T> std::ops::Index<Hex> for HexMap<T> {
+    type Output = T;
+
+    fn index(&self, pos: Hex) -> &Self::Output {
+        match self.get(pos) {
+            Some(v) => v,
+            None => panic!("no cell at {pos:?}"),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<Hex> for HexMap<T> {
+    fn index_mut(&mut self, pos: Hex) -> &mut Self::Output {
+        match self.get_mut(pos) {
+            Some(v) => v,
+            None => panic!("no cell at {pos:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let origin = Hex::default();
+        for d in HexDirection::ALL {
+            assert_eq!(Hex::from(d).norm(), 1);
+            assert_eq!(Hex::from(d) + Hex::from(d.opposite()), origin);
+            assert_eq!(d.turn_right().turn_left(), d);
+        }
+        assert_eq!(hex(3, -1).distance(hex(-2, 4)), 5);
+        assert_eq!(hex(2, 2).norm(), 4);
+        assert_eq!(hex(2, -5).cube(), (2, -5, 3));
+        assert_eq!(Hex::from_cube(2, -5, 3), hex(2, -5));
+        assert_eq!(origin.six_adjacent_iter().count(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a cube coordinate")]
+    fn invalid_cube() {
+        Hex::from_cube(1, 1, 1);
+    }
+
+    #[test]
+    fn rings() {
+        let center = hex(4, -7);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), vec![center]);
+        for radius in 1..8u32 {
+            let ring = center.ring(radius).collect::<Vec<_>>();
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+            assert!(ring.iter().all(|h| h.distance(center) == radius as i64));
+            assert_eq!(
+                ring[0],
+                center + Hex::from(HexDirection::North) * radius as i64
+            );
+            // Consecutive cells are adjacent, the ring being closed.
+            for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+                assert_eq!(a.distance(*b), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn spirals() {
+        let center = hex(-3, 2);
+        for radius in 0..8u32 {
+            let spiral = center.spiral(radius).collect::<HashSet<_>>();
+            let r = radius as usize;
+            assert_eq!(spiral.len(), 3 * r * (r + 1) + 1);
+            assert!(spiral.iter().all(|h| h.distance(center) <= radius as i64));
+        }
+        assert_eq!(center.spiral(3).next(), Some(center));
+    }
+
+    #[test]
+    fn paths() {
+        let path = parse_path("ne,ne,s,s\n").unwrap();
+        assert_eq!(
+            path,
+            vec![
+                HexDirection::NorthEast,
+                HexDirection::NorthEast,
+                HexDirection::South,
+                HexDirection::South
+            ]
+        );
+        let end = path.iter().map(|d| Hex::from(*d)).sum::<Hex>();
+        assert_eq!(end.norm(), 2);
+
+        let path = HexDirection::ALL.repeat(2);
+        let text = path
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(parse_path(&text), Ok(path));
+        assert_eq!(
+            parse_path(" se , sw "),
+            Ok(vec![HexDirection::SouthEast, HexDirection::SouthWest])
+        );
+
+        for bad in ["", "  ", "ne,,s", "ne,", "north", "NE", "ne s"] {
+            assert!(parse_path(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn hex_map() {
+        let mut map: HexMap<char> = hex(0, 0)
+            .spiral(1)
+            .map(|h| (h, if h == hex(0, 0) { 'c' } else { 'x' }))
+            .collect();
+        assert_eq!(map.len(), 7);
+        assert_eq!(map.six_adjacent_iter(hex(0, 0)).count(), 6);
+        assert_eq!(map.six_adjacent_pos_iter(hex(0, -1)).count(), 3);
+        assert_eq!(map.remove(hex(1, 0)), Some('x'));
+        assert_eq!(map.six_adjacent_iter(hex(0, 0)).count(), 5);
+        map[hex(0, 0)] = 'C';
+        assert_eq!(map.get(hex(0, 0)), Some(&'C'));
+        assert_eq!(map.get(hex(5, 5)), None);
+    }
+}
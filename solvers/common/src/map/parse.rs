@@ -0,0 +1,183 @@
+use chumsky::prelude::*;
+
+use super::{vec2, Map, Vec2};
+
+/// Cell types that can be read from a single character of a grid.
+pub trait FromGridChar: Sized {
+    fn from_grid_char(c: char) -> Option<Self>;
+}
+
+impl FromGridChar for char {
+    fn from_grid_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+/// Raw ASCII byte of the character.
+impl FromGridChar for u8 {
+    fn from_grid_char(c: char) -> Option<Self> {
+        c.is_ascii().then_some(c as u8)
+    }
+}
+
+/// Decimal digit, as found in height maps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Digit(pub u8);
+
+impl FromGridChar for Digit {
+    fn from_grid_char(c: char) -> Option<Self> {
+        c.to_digit(10).map(|d| Digit(d as u8))
+    }
+}
+
+impl std::fmt::Display for Digit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// `#` is `true` and `.` is `false`.
+impl FromGridChar for bool {
+    fn from_grid_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    UnknownChar {
+        line: usize,
+        column: usize,
+        c: char,
+    },
+    Ragged {
+        line: usize,
+        len: usize,
+        expected: usize,
+    },
+    MissingMarker(char),
+}
+
+impl std::fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::UnknownChar { line, column, c } => {
+                write!(f, "Unknown character `{c}` at line {line} column {column}")
+            }
+            GridParseError::Ragged {
+                line,
+                len,
+                expected,
+            } => write!(
+                f,
+                "Line {line} has {len} characters, expected {expected} like the first one"
+            ),
+            GridParseError::MissingMarker(c) => write!(f, "Marker `{c}` not found"),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+impl<T: FromGridChar> Map<T> {
+    /// Parses a grid, one line per row.
+    pub fn parse(input: &str) -> Result<Self, GridParseError> {
+        Self::parse_with(input, |_, c| T::from_grid_char(c))
+    }
+
+    /// Parses a grid and returns the position of each of the `markers`,
+    /// which must all be present. Markers are parsed as any other cell.
+    pub fn parse_with_markers<const N: usize>(
+        input: &str,
+        markers: [char; N],
+    ) -> Result<(Self, [Vec2; N]), GridParseError> {
+        let mut found = [None; N];
+        let map = Self::parse_with(input, |pos, c| {
+            if let Some(i) = markers.iter().position(|m| *m == c) {
+                found[i].get_or_insert(pos);
+            }
+            T::from_grid_char(c)
+        })?;
+
+        let mut positions = [Vec2::default(); N];
+        for (i, pos) in found.into_iter().enumerate() {
+            positions[i] = pos.ok_or(GridParseError::MissingMarker(markers[i]))?;
+        }
+
+        Ok((map, positions))
+    }
+}
+
+impl<T> Map<T> {
+    /// Parses a grid, `f` being given the position of each character.
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(Vec2, char) -> Option<T>,
+    ) -> Result<Self, GridParseError> {
+        let mut data = vec![];
+        let mut width = None;
+
+        for (y, line) in input.lines().enumerate() {
+            let mut len = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(vec2(x as i64, y as i64), c).ok_or(GridParseError::UnknownChar {
+                    line: y + 1,
+                    column: x + 1,
+                    c,
+                })?;
+                data.push(cell);
+                len += 1;
+            }
+
+            match width {
+                None => width = Some(len),
+                Some(expected) if expected != len => {
+                    return Err(GridParseError::Ragged {
+                        line: y + 1,
+                        len,
+                        expected,
+                    })
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Map::from_vec(width.unwrap_or(0), data).expect("rows have the same length"))
+    }
+}
+
+/// Parses a grid up to the end of input or an empty line, for inputs mixing
+/// a grid and other sections.
+pub fn grid_parser<T: FromGridChar>() -> impl Parser<char, Map<T>, Error = Simple<char>> {
+    let cell = filter_map(|span, c: char| {
+        if c == '\n' || c == '\r' {
+            return Err(Simple::expected_input_found(span, None, Some(c)));
+        }
+        T::from_grid_char(c)
+            .ok_or_else(|| Simple::custom(span, format!("Unknown grid character `{c}`")))
+    });
+
+    cell.repeated()
+        .at_least(1)
+        .separated_by(text::newline())
+        .try_map(|rows, span| Map::try_new(rows).map_err(|e| Simple::custom(span, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_and_digits() {
+        let bytes = Map::<u8>::parse("a#\n.7").unwrap();
+        assert_eq!(bytes.as_slice(), b"a#.7");
+
+        let digits = Map::<Digit>::parse("09\n17").unwrap();
+        assert_eq!(digits[vec2(1, 0)], Digit(9));
+        assert!(Map::<Digit>::parse("0a").is_err());
+    }
+}
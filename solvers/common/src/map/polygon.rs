@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use crate::math_helper::{gcd, Rational};
+
+use super::{vec2, Vec2};
+
+/// Closed polygon on the integer grid, the last vertex being linked back to
+/// the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Vec2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Vec2>) -> Self {
+        Polygon { vertices }
+    }
+
+    /// Polygon drawn by following `moves` from `start`, such as the `R 6`,
+    /// `D 5` instructions of a dig plan.
+    pub fn from_moves(
+        start: Vec2,
+        moves: impl IntoIterator<Item = (impl Into<Vec2>, i64)>,
+    ) -> Self {
+        let mut pos = start;
+        let mut vertices = vec![];
+        for (direction, length) in moves {
+            vertices.push(pos);
+            pos = pos + direction.into() * length;
+        }
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the area, which unlike the area is always an integer.
+    pub fn double_area(&self) -> i64 {
+        shoelace_double_area(self.edges())
+    }
+
+    /// Number of grid points on the edges.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| gcd((b.x - a.x).abs(), (b.y - a.y).abs()))
+            .sum()
+    }
+
+    /// Number of grid points strictly inside, e.g. the tiles enclosed by a
+    /// loop going through tile centers.
+    pub fn interior_points(&self) -> i64 {
+        pick_interior_points(self.double_area(), self.boundary_points())
+    }
+
+    /// Number of grid points inside or on the edges, e.g. the tiles covered
+    /// by a loop and what it encloses.
+    pub fn lattice_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Grid points on the edges, see [`Polygon::boundary_points`].
+    pub fn boundary(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.edges().flat_map(|(a, b)| {
+            let steps = gcd((b.x - a.x).abs(), (b.y - a.y).abs());
+            let step = (b - a) / steps.max(1);
+            (0..steps).map(move |i| a + step * i)
+        })
+    }
+
+    /// Grid points strictly inside, row by row, see
+    /// [`Polygon::interior_points`].
+    pub fn interior(&self) -> Vec<Vec2> {
+        let boundary = self.boundary().collect::<HashSet<_>>();
+        let min_y = self.vertices.iter().map(|v| v.y).min().unwrap_or(0);
+        let max_y = self.vertices.iter().map(|v| v.y).max().unwrap_or(-1);
+
+        let mut interior = vec![];
+        for y in min_y..=max_y {
+            // Even-odd rule, an edge being crossed by the row when it goes
+            // through its top end but not its bottom one.
+            let mut crossings = self
+                .edges()
+                .filter(|(a, b)| a.y.min(b.y) <= y && y < a.y.max(b.y))
+                .map(|(a, b)| {
+                    let dx = (y - a.y) as i128 * (b.x - a.x) as i128;
+                    Rational::from(a.x as i128) + Rational::new(dx, (b.y - a.y) as i128)
+                })
+                .collect::<Vec<_>>();
+            crossings.sort();
+
+            for pair in crossings.chunks_exact(2) {
+                let from = pair[0].ceil().to_integer() as i64;
+                let to = pair[1].floor().to_integer() as i64;
+                interior.extend(
+                    (from..=to)
+                        .map(|x| vec2(x, y))
+                        .filter(|pos| !boundary.contains(pos)),
+                );
+            }
+        }
+        interior
+    }
+}
+
+/// Shoelace formula, for the polygon made of `edges`.
+pub fn shoelace_double_area(edges: impl IntoIterator<Item = (Vec2, Vec2)>) -> i64 {
+    edges
+        .into_iter()
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .abs()
+}
+
+/// Pick's theorem, giving the number of grid points strictly inside a
+/// polygon from its doubled area and its number of boundary points. Polygons
+/// without any area have no inside.
+pub fn pick_interior_points(double_area: i64, boundary_points: i64) -> i64 {
+    if double_area == 0 {
+        return 0;
+    }
+    (double_area - boundary_points + 2) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{vec2, Direction};
+
+    #[test]
+    fn dig_plan() {
+        let plan = "R 6,D 5,L 2,D 2,R 2,D 2,L 5,U 2,L 1,U 2,R 2,U 3,L 2,U 2";
+        let polygon = Polygon::from_moves(
+            Vec2::default(),
+            plan.split(',').map(|m| {
+                let (d, n) = m.split_once(' ').unwrap();
+                let d = Direction::from_char(d.chars().next().unwrap()).unwrap();
+                (d, n.parse::<i64>().unwrap())
+            }),
+        );
+        assert_eq!(polygon.vertices().len(), 14);
+        assert_eq!(polygon.boundary_points(), 38);
+        assert_eq!(polygon.lattice_points(), 62);
+    }
+
+    #[test]
+    fn orientation_and_diagonals() {
+        let square = vec![vec2(0, 0), vec2(4, 0), vec2(4, 4), vec2(0, 4)];
+        let reversed = Polygon::new(square.iter().rev().copied().collect());
+        let square = Polygon::new(square);
+        assert_eq!(square.double_area(), 32);
+        assert_eq!(reversed.double_area(), 32);
+        assert_eq!(square.interior_points(), 9);
+
+        let triangle = Polygon::new(vec![vec2(0, 0), vec2(4, 0), vec2(0, 4)]);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 3);
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        let empty = Polygon::new(vec![]);
+        assert_eq!(empty.double_area(), 0);
+        assert_eq!(empty.boundary_points(), 0);
+        assert_eq!(empty.interior_points(), 0);
+        assert_eq!(empty.lattice_points(), 0);
+        assert!(empty.interior().is_empty());
+
+        let point = Polygon::new(vec![vec2(3, 2)]);
+        assert_eq!(point.interior_points(), 0);
+        assert!(point.interior().is_empty());
+
+        let segment = Polygon::new(vec![vec2(0, 0), vec2(4, 2)]);
+        assert_eq!(segment.interior_points(), 0);
+        assert!(segment.interior().is_empty());
+        assert_eq!(
+            segment.boundary().collect::<Vec<_>>(),
+            [vec2(0, 0), vec2(2, 1), vec2(4, 2), vec2(2, 1)]
+        );
+    }
+
+    #[test]
+    fn interior() {
+        let square = Polygon::new(vec![vec2(0, 0), vec2(3, 0), vec2(3, 3), vec2(0, 3)]);
+        assert_eq!(
+            square.interior(),
+            [vec2(1, 1), vec2(2, 1), vec2(1, 2), vec2(2, 2)]
+        );
+
+        // Diagonal edges crossing rows between grid points.
+        let triangle = Polygon::new(vec![vec2(0, 0), vec2(4, 0), vec2(0, 4)]);
+        assert_eq!(triangle.interior(), [vec2(1, 1), vec2(2, 1), vec2(1, 2)]);
+        let diamond = Polygon::new(vec![vec2(2, 0), vec2(5, 1), vec2(3, 4), vec2(0, 3)]);
+        assert_eq!(diamond.interior().len() as i64, diamond.interior_points());
+
+        // Vertices and horizontal edges lying on the scanned rows.
+        let polygon = Polygon::from_moves(
+            vec2(0, 0),
+            [
+                (Vec2::EAST, 6),
+                (Vec2::SOUTH, 4),
+                (Vec2::WEST, 2),
+                (Vec2::NORTH, 2),
+                (Vec2::WEST, 2),
+                (Vec2::SOUTH, 2),
+                (Vec2::WEST, 2),
+                (Vec2::NORTH, 4),
+            ],
+        );
+        let interior = polygon.interior();
+        assert_eq!(interior.len() as i64, polygon.interior_points());
+        assert!(interior.contains(&vec2(1, 3)));
+        assert!(interior.contains(&vec2(5, 3)));
+        assert!(!interior.contains(&vec2(3, 3)));
+        assert!(!interior.contains(&vec2(3, 2)));
+    }
+}
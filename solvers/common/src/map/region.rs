@@ -0,0 +1,385 @@
+use std::collections::HashSet;
+
+use super::{vec2, Map, Polygon, Vec2};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub cells: Vec<Vec2>,
+    /// Top left and bottom right cells of the bounding box, both included.
+    pub min: Vec2,
+    pub max: Vec2,
+    connectivity: Connectivity,
+    perimeter: usize,
+    corners: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges bordering another region or the outside.
+    ///
+    /// Like [`Region::corners`], only defined for regions labelled with
+    /// [`Connectivity::Four`], panics otherwise.
+    pub fn perimeter(&self) -> usize {
+        self.assert_four_connected();
+        self.perimeter
+    }
+
+    /// Number of corners of the region, holes included, a cell touching
+    /// another region by a corner counting as a corner.
+    pub fn corners(&self) -> usize {
+        self.assert_four_connected();
+        self.corners
+    }
+
+    /// Number of straight sides, which is also the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    fn assert_four_connected(&self) {
+        assert_eq!(
+            self.connectivity,
+            Connectivity::Four,
+            "perimeter and sides are only defined for 4-connected regions"
+        );
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Regions {
+    labels: Map<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Index of the region `pos` belongs to, if any.
+    pub fn label(&self, pos: Vec2) -> Option<usize> {
+        self.labels.get(pos).copied().flatten()
+    }
+
+    pub fn labels(&self) -> &Map<Option<usize>> {
+        &self.labels
+    }
+
+    pub fn region_of(&self, pos: Vec2) -> Option<&Region> {
+        self.label(pos).map(|l| &self.regions[l])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+impl std::ops::Index<usize> for Regions {
+    type Output = Region;
+
+    fn index(&self, label: usize) -> &Self::Output {
+        &self.regions[label]
+    }
+}
+
+impl<T> Map<T> {
+    /// Splits the whole map in regions, adjacent cells being in the same
+    /// region when `connected` holds.
+    pub fn regions_by(
+        &self,
+        connectivity: Connectivity,
+        connected: impl Fn(&T, &T) -> bool,
+    ) -> Regions {
+        self.label_regions(connectivity, |_| true, connected)
+    }
+
+    /// Regions made of adjacent cells matching `predicate`, other cells
+    /// belonging to none.
+    pub fn regions_where(
+        &self,
+        connectivity: Connectivity,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Regions {
+        self.label_regions(connectivity, &predicate, |_, _| true)
+    }
+
+    /// Cells of the region containing `start`, see [`Map::regions_by`],
+    /// none when `start` is outside the map.
+    pub fn flood_fill(
+        &self,
+        start: Vec2,
+        connectivity: Connectivity,
+        connected: impl Fn(&T, &T) -> bool,
+    ) -> HashSet<Vec2> {
+        if !self.contains(start) {
+            return HashSet::new();
+        }
+
+        let mut visited = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for next in self.connected_neighbors(pos, connectivity) {
+                if connected(&self[pos], &self[next]) && visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Cells strictly inside `cycle`, a closed path of adjacent cells, see
+    /// [`Polygon::interior`].
+    pub fn enclosed_by_loop(&self, cycle: &[Vec2]) -> Vec<Vec2> {
+        Polygon::new(cycle.to_vec())
+            .interior()
+            .into_iter()
+            .filter(|&pos| self.contains(pos))
+            .collect()
+    }
+
+    fn connected_neighbors(
+        &self,
+        pos: Vec2,
+        connectivity: Connectivity,
+    ) -> Box<dyn Iterator<Item = Vec2> + '_> {
+        match connectivity {
+            Connectivity::Four => Box::new(self.four_adjacent_pos_iter(pos)),
+            Connectivity::Eight => Box::new(self.eight_adjacent_pos_iter(pos)),
+        }
+    }
+
+    fn label_regions(
+        &self,
+        connectivity: Connectivity,
+        include: impl Fn(&T) -> bool,
+        connected: impl Fn(&T, &T) -> bool,
+    ) -> Regions {
+        let mut labels = Map::filled(self.width(), self.height(), None);
+        let mut regions = vec![];
+
+        for (start, cell) in self.iter() {
+            if labels[start].is_some() || !include(cell) {
+                continue;
+            }
+
+            let label = regions.len();
+            labels[start] = Some(label);
+            let mut cells = vec![];
+            let mut stack = vec![start];
+            while let Some(pos) = stack.pop() {
+                cells.push(pos);
+                for next in self.connected_neighbors(pos, connectivity) {
+                    if labels[next].is_none()
+                        && include(&self[next])
+                        && connected(&self[pos], &self[next])
+                    {
+                        labels[next] = Some(label);
+                        stack.push(next);
+                    }
+                }
+            }
+
+            let in_region = |p: Vec2| labels.get(p).copied().flatten() == Some(label);
+            let perimeter = cells
+                .iter()
+                .flat_map(|p| p.four_adjacent_iter())
+                .filter(|p| !in_region(*p))
+                .count();
+            let corners = cells
+                .iter()
+                .map(|&p| {
+                    [
+                        (Vec2::NORTH, Vec2::EAST),
+                        (Vec2::EAST, Vec2::SOUTH),
+                        (Vec2::SOUTH, Vec2::WEST),
+                        (Vec2::WEST, Vec2::NORTH),
+                    ]
+                    .into_iter()
+                    .filter(|&(a, b)| {
+                        let (a_in, b_in) = (in_region(p + a), in_region(p + b));
+                        let convex = !a_in && !b_in;
+                        let concave = a_in && b_in && !in_region(p + a + b);
+                        convex || concave
+                    })
+                    .count()
+                })
+                .sum();
+
+            let min = vec2(
+                cells.iter().map(|p| p.x).min().unwrap(),
+                cells.iter().map(|p| p.y).min().unwrap(),
+            );
+            let max = vec2(
+                cells.iter().map(|p| p.x).max().unwrap(),
+                cells.iter().map(|p| p.y).max().unwrap(),
+            );
+
+            regions.push(Region {
+                cells,
+                min,
+                max,
+                connectivity,
+                perimeter,
+                corners,
+            });
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Direction;
+
+    fn price(map: &str, bulk: bool) -> usize {
+        let map = Map::<char>::parse(map).unwrap();
+        map.regions_by(Connectivity::Four, |a, b| a == b)
+            .iter()
+            .map(|r| r.area() * if bulk { r.sides() } else { r.perimeter() })
+            .sum()
+    }
+
+    #[test]
+    fn perimeter_and_sides() {
+        let map = "AAAA\nBBCD\nBBCC\nEEEC";
+        assert_eq!(price(map, false), 140);
+        assert_eq!(price(map, true), 80);
+
+        let map = Map::<char>::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let regions = map.regions_by(Connectivity::Four, |a, b| a == b);
+        let c = regions.region_of(vec2(2, 1)).unwrap();
+        assert_eq!((c.area(), c.perimeter(), c.sides()), (4, 10, 8));
+        assert_eq!((c.min, c.max), (vec2(2, 1), vec2(3, 3)));
+        let d = regions.region_of(vec2(3, 1)).unwrap();
+        assert_eq!((d.area(), d.perimeter(), d.sides()), (1, 4, 4));
+    }
+
+    #[test]
+    fn holes() {
+        // The O region surrounds the X ones, whose edges and corners count in
+        // both.
+        let map = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO";
+        assert_eq!(price(map, false), 772);
+        assert_eq!(price(map, true), 436);
+
+        let map = Map::<char>::parse("OOOO\nO..O\nO..O\nOOOO").unwrap();
+        let regions = map.regions_where(Connectivity::Four, |c| *c == 'O');
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].area(), 12);
+        assert_eq!(regions[0].perimeter(), 24);
+        assert_eq!(regions[0].sides(), 8);
+        assert_eq!(regions.label(vec2(1, 1)), None);
+    }
+
+    #[test]
+    fn diagonal_touches() {
+        // The two B regions touch each other, and the A region touches itself,
+        // by a corner.
+        let map = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA";
+        assert_eq!(price(map, true), 368);
+
+        let map = Map::<char>::parse(map).unwrap();
+        let regions = map.regions_by(Connectivity::Four, |a, b| a == b);
+        assert_eq!(regions.len(), 3);
+        let a = regions.region_of(vec2(0, 0)).unwrap();
+        assert_eq!((a.area(), a.perimeter(), a.sides()), (28, 40, 12));
+
+        let map = Map::<char>::parse("X.\n.X").unwrap();
+        let regions = map.regions_where(Connectivity::Four, |c| *c == 'X');
+        assert_eq!(regions.len(), 2);
+        assert!(regions.iter().all(|r| r.perimeter() == 4 && r.sides() == 4));
+    }
+
+    #[test]
+    fn eight_connectivity() {
+        let map = Map::<char>::parse("X..\n.X.\n..X\nX..").unwrap();
+        let regions = map.regions_where(Connectivity::Eight, |c| *c == 'X');
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].area(), 3);
+        assert_eq!((regions[0].min, regions[0].max), (vec2(0, 0), vec2(2, 2)));
+        assert_eq!(regions.label(vec2(0, 3)), Some(1));
+        assert_eq!(regions.label(vec2(1, 0)), None);
+
+        let region = map.flood_fill(vec2(0, 0), Connectivity::Eight, |a, b| a == b);
+        assert_eq!(region.len(), 3);
+        let region = map.flood_fill(vec2(0, 0), Connectivity::Four, |a, b| a == b);
+        assert_eq!(region, HashSet::from([vec2(0, 0)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "only defined for 4-connected regions")]
+    fn sides_of_eight_connected_regions() {
+        let map = Map::<char>::parse("X.\n.X").unwrap();
+        map.regions_where(Connectivity::Eight, |c| *c == 'X')[0].sides();
+    }
+
+    #[test]
+    fn flood_fill_outside_the_map() {
+        let map = Map::<char>::parse("ab\ncd").unwrap();
+        for start in [vec2(-1, 0), vec2(2, 1), vec2(0, 5)] {
+            assert!(map
+                .flood_fill(start, Connectivity::Four, |_, _| true)
+                .is_empty());
+        }
+        assert_eq!(
+            map.flood_fill(vec2(1, 1), Connectivity::Four, |_, _| true)
+                .len(),
+            4
+        );
+        assert!(Map::<char>::new(vec![])
+            .flood_fill(vec2(0, 0), Connectivity::Eight, |_, _| true)
+            .is_empty());
+    }
+
+    fn trace(start: Vec2, moves: &str) -> Vec<Vec2> {
+        let mut cycle = vec![];
+        let mut pos = start;
+        for m in moves.split(' ') {
+            let direction = Direction::from_char(m.chars().next().unwrap()).unwrap();
+            for _ in 0..m[1..].parse::<usize>().unwrap() {
+                cycle.push(pos);
+                pos = pos + Vec2::from(direction);
+            }
+        }
+        assert_eq!(pos, start);
+        cycle
+    }
+
+    #[test]
+    fn enclosed_by_loop() {
+        let map = Map::filled(11, 9, ());
+        let rectangle = trace(vec2(1, 1), "R3 D2 L3 U2");
+        assert_eq!(map.enclosed_by_loop(&rectangle), [vec2(2, 2), vec2(3, 2)]);
+
+        // Loop of 2023 day 10, the tiles between the two inner pipes are not
+        // enclosed.
+        let cycle = trace(vec2(1, 1), "R8 D6 L3 U2 R2 U3 L6 D3 R2 D2 L3 U6");
+        let enclosed = map.enclosed_by_loop(&cycle);
+        assert_eq!(enclosed, [vec2(2, 6), vec2(3, 6), vec2(7, 6), vec2(8, 6)]);
+        assert_eq!(
+            Polygon::new(cycle.clone()).interior_points(),
+            enclosed.len() as i64
+        );
+        assert!(enclosed.iter().all(|pos| !cycle.contains(pos)));
+
+        // Only the enclosed cells lying on the map are kept.
+        assert_eq!(Map::filled(3, 3, ()).enclosed_by_loop(&cycle), []);
+        assert!(map.enclosed_by_loop(&[]).is_empty());
+        assert!(map.enclosed_by_loop(&[vec2(4, 4)]).is_empty());
+    }
+}
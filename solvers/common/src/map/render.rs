@@ -0,0 +1,279 @@
+use std::{collections::HashMap, fmt::Write, io::IsTerminal};
+
+use super::{vec2, Direction, Map, Vec2};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 97,
+            Color::Gray => 90,
+        }
+    }
+}
+
+type CellFn<'a, T, R> = Box<dyn Fn(&T) -> R + 'a>;
+
+/// Builds an annotated rendering of a [`Map`]. In solvers, only build it
+/// when debugging so that timed runs do not pay for it.
+///
+/// ```ignore
+/// if debug::enabled() {
+///     Renderer::new(&map)
+///         .color_by(|c| (*c == Cell::Wall).then_some(Color::Gray))
+///         .path(&best_path, Color::Green)
+///         .rulers(true)
+///         .debug_print();
+/// }
+/// ```
+pub struct Renderer<'a, T> {
+    map: &'a Map<T>,
+    cell: CellFn<'a, T, String>,
+    color: CellFn<'a, T, Option<Color>>,
+    overlay: HashMap<Vec2, (char, Option<Color>)>,
+    window: Option<(Vec2, Vec2)>,
+    rulers: bool,
+    colored: bool,
+}
+
+impl<'a, T: std::fmt::Display> Renderer<'a, T> {
+    pub fn new(map: &'a Map<T>) -> Self {
+        Self::with_cells(map, |c| c.to_string())
+    }
+}
+
+impl<'a, T> Renderer<'a, T> {
+    /// Renders cells with `cell` instead of their `Display` implementation.
+    pub fn with_cells(map: &'a Map<T>, cell: impl Fn(&T) -> String + 'a) -> Self {
+        Renderer {
+            map,
+            cell: Box::new(cell),
+            color: Box::new(|_| None),
+            overlay: HashMap::new(),
+            window: None,
+            rulers: false,
+            colored: std::io::stderr().is_terminal(),
+        }
+    }
+
+    /// Colours cells according to their value.
+    pub fn color_by(mut self, color: impl Fn(&T) -> Option<Color> + 'a) -> Self {
+        self.color = Box::new(color);
+        self
+    }
+
+    /// Draws `glyph` over `points`, on top of previous overlays.
+    pub fn points(
+        mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        glyph: char,
+        color: Option<Color>,
+    ) -> Self {
+        for p in points {
+            self.overlay.insert(p, (glyph, color));
+        }
+        self
+    }
+
+    /// Draws `path` with arrows pointing to the next position, `*` when it
+    /// is not adjacent.
+    pub fn path(mut self, path: &[Vec2], color: Color) -> Self {
+        let mut last_glyph = '*';
+        for (i, &p) in path.iter().enumerate() {
+            if let Some(&next) = path.get(i + 1) {
+                last_glyph = Direction::try_from(next - p).map_or('*', |d| d.to_arrow());
+            }
+            self.overlay.insert(p, (last_glyph, Some(color)));
+        }
+        self
+    }
+
+    /// Only renders cells between `min` and `max`, both included.
+    pub fn crop(mut self, min: Vec2, max: Vec2) -> Self {
+        self.window = Some((min, max));
+        self
+    }
+
+    /// Shows coordinates above and on the left of the map.
+    pub fn rulers(mut self, rulers: bool) -> Self {
+        self.rulers = rulers;
+        self
+    }
+
+    /// Uses ANSI colours, enabled by default when stderr is a terminal.
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    /// Prints the rendering on stderr if debugging is enabled, see
+    /// [`crate::debug`].
+    pub fn debug_print(&self) {
+        if crate::debug::enabled() {
+            eprintln!("{self}");
+        }
+    }
+
+    fn paint(&self, f: &mut impl Write, s: &str, color: Option<Color>) -> std::fmt::Result {
+        match color {
+            Some(color) if self.colored => write!(f, "\x1b[{}m{s}\x1b[0m", color.ansi_code()),
+            _ => f.write_str(s),
+        }
+    }
+}
+
+impl<T> std::fmt::Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (min, max) = self
+            .window
+            .unwrap_or((Vec2::default(), self.map.size() - 1));
+        let min = vec2(min.x.max(0), min.y.max(0));
+        let max = vec2(
+            max.x.min(self.map.width() as i64 - 1),
+            max.y.min(self.map.height() as i64 - 1),
+        );
+        if min.x > max.x || min.y > max.y {
+            return Ok(());
+        }
+
+        let margin = max.y.to_string().len();
+        if self.rulers {
+            let digits = max.x.to_string().len();
+            for d in (0..digits).rev() {
+                write!(f, "{:margin$} ", "")?;
+                for x in min.x..=max.x {
+                    let place = 10i64.pow(d as u32);
+                    if d > 0 && x < place {
+                        f.write_char(' ')?;
+                    } else {
+                        write!(f, "{}", x / place % 10)?;
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+
+        for y in min.y..=max.y {
+            if self.rulers {
+                write!(f, "{y:>margin$} ")?;
+            }
+            for x in min.x..=max.x {
+                let pos = vec2(x, y);
+                match self.overlay.get(&pos) {
+                    Some((glyph, color)) => self.paint(f, &glyph.to_string(), *color)?,
+                    None => {
+                        let cell = &self.map[pos];
+                        self.paint(f, &(self.cell)(cell), (self.color)(cell))?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Map<char> {
+        Map::new(
+            (0..12)
+                .map(|y| {
+                    (0..12)
+                        .map(|x| if (x + y) % 5 == 0 { '#' } else { '.' })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn plain_output() {
+        let map = Map::new(vec![vec!['.', '#'], vec!['#', '.']]);
+        let renderer = Renderer::new(&map)
+            .color_by(|c| (*c == '#').then_some(Color::Red))
+            .colored(false);
+        assert_eq!(renderer.to_string(), ".#\n#.\n");
+
+        let renderer = Renderer::with_cells(&map, |c| if *c == '#' { "██" } else { "  " }.into())
+            .colored(false);
+        assert_eq!(renderer.to_string(), "  ██\n██  \n");
+    }
+
+    #[test]
+    fn colored_output() {
+        let map = Map::new(vec![vec!['.', '#']]);
+        let renderer = Renderer::new(&map)
+            .color_by(|c| (*c == '#').then_some(Color::Red))
+            .points([vec2(0, 0)], 'o', Some(Color::Green))
+            .colored(true);
+        assert_eq!(renderer.to_string(), "\x1b[32mo\x1b[0m\x1b[31m#\x1b[0m\n");
+    }
+
+    #[test]
+    fn overlays() {
+        let map = Map::filled(4, 3, '.');
+        let path = [vec2(0, 0), vec2(1, 0), vec2(1, 1), vec2(3, 2)];
+        let renderer = Renderer::new(&map)
+            .path(&path, Color::Green)
+            .points([vec2(3, 0), vec2(3, 2)], 'X', None)
+            .colored(false);
+        assert_eq!(renderer.to_string(), ">v.X\n.*..\n...X\n");
+    }
+
+    #[test]
+    fn crop() {
+        let map = sample();
+        let renderer = Renderer::new(&map)
+            .crop(vec2(1, 2), vec2(4, 3))
+            .colored(false);
+        assert_eq!(renderer.to_string(), "..#.\n.#..\n");
+
+        // The window is clamped to the map.
+        let renderer = Renderer::new(&map)
+            .crop(vec2(-5, 10), vec2(1, 20))
+            .colored(false);
+        assert_eq!(renderer.to_string(), "#.\n..\n");
+
+        let renderer = Renderer::new(&map)
+            .crop(vec2(20, 0), vec2(30, 5))
+            .colored(false);
+        assert_eq!(renderer.to_string(), "");
+        assert_eq!(Renderer::new(&Map::<char>::new(vec![])).to_string(), "");
+    }
+
+    #[test]
+    fn rulers() {
+        let map = sample();
+        let renderer = Renderer::new(&map)
+            .crop(vec2(8, 9), vec2(11, 10))
+            .rulers(true)
+            .colored(false);
+        let expected = concat!(
+            "     11\n", //
+            "   8901\n",
+            " 9 ...#\n",
+            "10 ..#.\n",
+        );
+        assert_eq!(renderer.to_string(), expected);
+    }
+}
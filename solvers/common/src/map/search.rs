@@ -0,0 +1,311 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use super::{Map, Vec2};
+
+/// Outcome of a search: cost of every reached state along with the
+/// predecessors it can be reached from on a shortest path.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    distances: HashMap<S, u64>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, u64> {
+        &self.distances
+    }
+
+    /// Goal states reached with the lowest cost, empty if none was reached.
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goals.first().and_then(|g| self.distance(g))
+    }
+
+    /// One of the shortest paths from a start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+
+        // Starts are the only states without predecessors, distances alone
+        // cannot tell them apart when moves may cost nothing.
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous[0].clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All states lying on a shortest path from a start to `state`.
+    pub fn states_on_shortest_paths(&self, state: &S) -> HashSet<S> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![];
+        if self.distances.contains_key(state) {
+            stack.push(state.clone());
+        }
+
+        while let Some(s) = stack.pop() {
+            if !visited.insert(s.clone()) {
+                continue;
+            }
+            if let Some(previous) = self.predecessors.get(&s) {
+                stack.extend(previous.iter().cloned());
+            }
+        }
+
+        visited
+    }
+
+    /// Lowest distance to reach each cell of `map`, `pos` telling where a
+    /// state stands.
+    pub fn distance_map<T>(&self, map: &Map<T>, pos: impl Fn(&S) -> Vec2) -> Map<Option<u64>> {
+        let mut distances = Map::filled(map.width(), map.height(), None);
+        for (state, d) in &self.distances {
+            if let Some(cell) = distances.get_mut(pos(state)) {
+                *cell = Some(cell.map_or(*d, |c: u64| c.min(*d)));
+            }
+        }
+        distances
+    }
+}
+
+/// Breadth first search, every move costing 1. The search stops once all
+/// goals at the lowest distance have been reached.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search {
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+        goals: vec![],
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, d)) = queue.pop_front() {
+        if search.goal_distance().is_some_and(|g| d > g) {
+            break;
+        }
+        if is_goal(&state) {
+            search.goals.push(state.clone());
+            continue;
+        }
+
+        for next in successors(&state) {
+            match search.distances.entry(next.clone()) {
+                Entry::Vacant(e) => {
+                    e.insert(d + 1);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    queue.push_back((next, d + 1));
+                }
+                Entry::Occupied(e) if *e.get() == d + 1 => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                Entry::Occupied(_) => (),
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm, see [`astar`].
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// A* search, `heuristic` must never overestimate the remaining cost. It
+/// does not need to be consistent, states are expanded again when a cheaper
+/// way to them is found. The search stops once all goals at the lowest cost
+/// have been reached, so that every shortest path is known.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut search = Search {
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+        goals: vec![],
+    };
+    let mut heap = BinaryHeap::new();
+    let mut start_states = HashSet::new();
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            start_states.insert(start.clone());
+            heap.push(Reverse((heuristic(&start), 0, start)));
+        }
+    }
+
+    while let Some(Reverse((estimate, d, state))) = heap.pop() {
+        if search.goal_distance().is_some_and(|g| estimate > g) {
+            break;
+        }
+        // A cheaper way to this state was found since it was queued.
+        if search.distances[&state] < d {
+            continue;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for (next, cost) in successors(&state) {
+            let next_d = d + cost;
+            match search.distances.entry(next.clone()) {
+                Entry::Occupied(e) if *e.get() < next_d => continue,
+                Entry::Occupied(e) if *e.get() == next_d => {
+                    if start_states.contains(&next) {
+                        continue;
+                    }
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                    continue;
+                }
+                Entry::Occupied(mut e) => {
+                    e.insert(next_d);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(next_d);
+                }
+            }
+            search
+                .predecessors
+                .insert(next.clone(), vec![state.clone()]);
+            heap.push(Reverse((next_d + heuristic(&next), next_d, next)));
+        }
+    }
+
+    search
+}
+
+impl<T> Map<T> {
+    /// Four adjacent positions of `pos` that can be moved to from it.
+    pub fn passable_neighbors<'a>(
+        &'a self,
+        pos: Vec2,
+        mut passable: impl FnMut(Vec2, &T, Vec2, &T) -> bool + 'a,
+    ) -> impl Iterator<Item = Vec2> + 'a {
+        let from = &self[pos];
+        self.four_adjacent_pos_iter(pos)
+            .filter(move |&to| passable(pos, from, to, &self[to]))
+    }
+
+    /// Distance from `start` to every cell, moving to adjacent cells for
+    /// which `passable(from, from_cell, to, to_cell)` holds.
+    pub fn bfs_distances(
+        &self,
+        start: Vec2,
+        mut passable: impl FnMut(Vec2, &T, Vec2, &T) -> bool,
+    ) -> Map<Option<u64>> {
+        bfs(
+            [start],
+            |&p| {
+                self.passable_neighbors(p, &mut passable)
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        )
+        .distance_map(self, |p| *p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_through_zero_cost_moves() {
+        // 0 -> 1 -> 2 -> 3 for free, 3 -> 4 costs 1, with a free way back.
+        let search = dijkstra(
+            [0],
+            |&s: &u32| match s {
+                0..=3 => vec![(s + 1, (s == 3) as u64), (s.saturating_sub(1), 0)],
+                _ => vec![],
+            },
+            |&s| s == 4,
+        );
+        assert_eq!(search.goal_distance(), Some(1));
+        assert_eq!(search.path_to(&4), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(search.path_to(&0), Some(vec![0]));
+    }
+
+    #[test]
+    fn admissible_inconsistent_heuristic() {
+        // Edges: 0 -1-> 1, 0 -3-> 2, 1 -1-> 2, 2 -3-> 3. The heuristic never
+        // overestimates but drops by 3 across the unit edge from 1 to 2, so
+        // 2 is first reached through the costly edge.
+        let search = astar(
+            [0],
+            |&s: &u32| match s {
+                0 => vec![(1, 1), (2, 3)],
+                1 => vec![(2, 1)],
+                2 => vec![(3, 3)],
+                _ => vec![],
+            },
+            |&s| [3, 4, 1, 0][s as usize],
+            |&s| s == 3,
+        );
+        assert_eq!(search.goal_distance(), Some(5));
+        assert_eq!(search.path_to(&3), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        let map = Map::<char>::parse("...\n.#.\n...").unwrap();
+        let search = bfs(
+            [Vec2::default()],
+            |&p| {
+                map.passable_neighbors(p, |_, _, _, c| *c == '.')
+                    .collect::<Vec<_>>()
+            },
+            |p| *p == map.size() - 1,
+        );
+        assert_eq!(search.goal_distance(), Some(4));
+        assert_eq!(search.states_on_shortest_paths(&(map.size() - 1)).len(), 8);
+        assert_eq!(search.path_to(&(map.size() - 1)).unwrap().len(), 5);
+    }
+}
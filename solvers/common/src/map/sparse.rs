@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use super::{vec2, Map, Vec2};
+
+/// Grid storing only its occupied cells, which can grow in any direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMap<T> {
+    cells: HashMap<Vec2, T>,
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl<T> Default for SparseMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseMap<T> {
+    pub fn new() -> Self {
+        SparseMap {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Top left and bottom right corners of the occupied cells, both
+    /// included.
+    pub fn bounding_box(&self) -> Option<(Vec2, Vec2)> {
+        self.bounds
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Vec2, value: T) -> Option<T> {
+        let corners = self.bounds.into_iter().flat_map(|(min, max)| [min, max]);
+        self.bounds = compute_bounds(corners.chain([pos]));
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Vec2) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        if let Some((min, max)) = self.bounds {
+            if pos.x == min.x || pos.y == min.y || pos.x == max.x || pos.y == max.y {
+                self.bounds = compute_bounds(self.cells.keys().copied());
+            }
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vec2, &mut T)> {
+        self.cells.iter_mut().map(|(p, v)| (*p, v))
+    }
+
+    /// Occupied cells met going from `start` in `direction`, until leaving
+    /// the bounding box, nothing if `start` is outside of it.
+    pub fn iter_from_point(
+        &self,
+        start: Vec2,
+        direction: Vec2,
+    ) -> impl Iterator<Item = (Vec2, &T)> {
+        let in_bounds = move |p: Vec2| {
+            self.bounds.is_some_and(|(min, max)| {
+                p.x >= min.x && p.y >= min.y && p.x <= max.x && p.y <= max.y
+            })
+        };
+        std::iter::successors(Some(start), move |p| Some(*p + direction))
+            .take_while(move |p| in_bounds(*p))
+            .filter_map(|p| self.get(p).map(|v| (p, v)))
+    }
+
+    pub fn eight_adjacent_iter(&self, pos: Vec2) -> impl Iterator<Item = &T> {
+        self.eight_adjacent_pos_iter(pos).map(|pos| &self[pos])
+    }
+
+    /// Occupied cells among the eight adjacent ones.
+    pub fn eight_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        Vec2::directions()
+            .into_iter()
+            .map(move |d| pos + d)
+            .filter(|&pos| self.contains(pos))
+    }
+
+    pub fn four_adjacent_iter(&self, pos: Vec2) -> impl Iterator<Item = &T> {
+        self.four_adjacent_pos_iter(pos).map(|pos| &self[pos])
+    }
+
+    /// Occupied cells among the four adjacent ones.
+    pub fn four_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        pos.four_adjacent_iter().filter(|&pos| self.contains(pos))
+    }
+
+    /// Dense map covering the bounding box, its top left cell being the
+    /// bounding box minimum.
+    pub fn to_map(&self, empty: T) -> Map<T>
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds else {
+            return Map::filled(0, 0, empty);
+        };
+        let size = max - min + 1;
+        let mut map = Map::filled(size.x as usize, size.y as usize, empty);
+        for (pos, value) in self.iter() {
+            map[pos - min] = value.clone();
+        }
+        map
+    }
+}
+
+fn compute_bounds(positions: impl Iterator<Item = Vec2>) -> Option<(Vec2, Vec2)> {
+    positions.fold(None, |bounds, p| {
+        Some(match bounds {
+            Some((min, max)) => (
+                vec2(min.x.min(p.x), min.y.min(p.y)),
+                vec2(max.x.max(p.x), max.y.max(p.y)),
+            ),
+            None => (p, p),
+        })
+    })
+}
+
+impl<T> FromIterator<(Vec2, T)> for SparseMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Vec2, T)>>(iter: I) -> Self {
+        let mut map = SparseMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T> Extend<(Vec2, T)> for SparseMap<T> {
+    fn extend<I: IntoIterator<Item = (Vec2, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+impl<T> From<Map<T>> for SparseMap<T> {
+    fn from(map: Map<T>) -> Self {
+        let width = map.width();
+        map.into_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (vec2((i % width) as i64, (i / width) as i64), v))
+            .collect()
+    }
+}
+
+/// Renders the bounding box like [`Map`], empty cells as `.`.
+impl<T: std::fmt::Display> std::fmt::Display for SparseMap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(vec2(x, y)) {
+                    Some(c) => write!(f, "{}", c)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> std::ops::Index<Vec2> for SparseMap<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        match self.get(pos) {
+            Some(v) => v,
+            None => panic!("no cell at {pos:?}"),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<Vec2> for SparseMap<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut Self::Output {
+        match self.get_mut(pos) {
+            Some(v) => v,
+            None => panic!("no cell at {pos:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_insertions_and_removals() {
+        let mut map = SparseMap::new();
+        assert_eq!(map.bounding_box(), None);
+        assert!(map.is_empty());
+
+        map.insert(vec2(2, 3), 'a');
+        assert_eq!(map.bounding_box(), Some((vec2(2, 3), vec2(2, 3))));
+        map.insert(vec2(-4, 5), 'b');
+        map.insert(vec2(0, -1), 'c');
+        assert_eq!(map.bounding_box(), Some((vec2(-4, -1), vec2(2, 5))));
+        assert_eq!(map.insert(vec2(0, -1), 'd'), Some('c'));
+        assert_eq!(map.len(), 3);
+
+        // Removing an inner cell keeps the bounds, an extreme one shrinks them.
+        map.insert(vec2(0, 0), 'e');
+        assert_eq!(map.remove(vec2(0, 0)), Some('e'));
+        assert_eq!(map.bounding_box(), Some((vec2(-4, -1), vec2(2, 5))));
+        assert_eq!(map.remove(vec2(-4, 5)), Some('b'));
+        assert_eq!(map.bounding_box(), Some((vec2(0, -1), vec2(2, 3))));
+        assert_eq!(map.remove(vec2(-4, 5)), None);
+        map.remove(vec2(0, -1));
+        map.remove(vec2(2, 3));
+        assert_eq!(map.bounding_box(), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let mut map: SparseMap<u8> = [(vec2(-3, -2), 1), (vec2(-2, -2), 2), (vec2(-3, 0), 3)]
+            .into_iter()
+            .collect();
+        assert_eq!(map[vec2(-2, -2)], 2);
+        map[vec2(-3, 0)] += 10;
+        assert_eq!(map.get(vec2(-3, 0)), Some(&13));
+        assert_eq!(map.get(vec2(3, 0)), None);
+
+        let mut neighbors = map.four_adjacent_pos_iter(vec2(-3, -1)).collect::<Vec<_>>();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![vec2(-3, -2), vec2(-3, 0)]);
+        assert_eq!(map.eight_adjacent_iter(vec2(-2, -1)).sum::<u8>(), 16);
+        assert_eq!(
+            map.iter_from_point(vec2(-3, -2), Vec2::SOUTH)
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![vec2(-3, -2), vec2(-3, 0)]
+        );
+        // Starting outside the bounding box, nothing is met.
+        assert_eq!(map.iter_from_point(vec2(-3, -5), Vec2::SOUTH).count(), 0);
+    }
+
+    #[test]
+    fn to_map_and_display() {
+        let map: SparseMap<char> = [(vec2(-1, -1), '#'), (vec2(1, 0), '@'), (vec2(0, 1), '#')]
+            .into_iter()
+            .collect();
+        assert_eq!(map.to_string(), "#..\n..@\n.#.\n");
+
+        let dense = map.to_map(' ');
+        assert_eq!(dense.size(), vec2(3, 3));
+        assert_eq!(dense.to_string(), "#  \n  @\n # \n");
+        assert_eq!(dense[vec2(2, 1)], '@');
+
+        let round_trip = SparseMap::from(Map::new(vec![vec!['a', 'b'], vec!['c', 'd']]));
+        assert_eq!(round_trip.to_string(), "ab\ncd\n");
+        assert_eq!(round_trip.bounding_box(), Some((vec2(0, 0), vec2(1, 1))));
+
+        let empty = SparseMap::<char>::new();
+        assert_eq!(empty.to_string(), "");
+        assert_eq!(empty.to_map('.').size(), vec2(0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "no cell at")]
+    fn index_empty_cell() {
+        let map = SparseMap::<u8>::new();
+        let _ = map[vec2(0, 0)];
+    }
+}
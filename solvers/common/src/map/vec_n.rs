@@ -0,0 +1,258 @@
+use super::Vec2;
+
+/// Position or offset in `N` dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VecN<const N: usize>(pub [i64; N]);
+
+pub type Vec3 = VecN<3>;
+
+pub fn vec3(x: impl Into<i64>, y: impl Into<i64>, z: impl Into<i64>) -> Vec3 {
+    VecN([x.into(), y.into(), z.into()])
+}
+
+impl Vec3 {
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+}
+
+impl<const N: usize> Default for VecN<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> VecN<N> {
+    pub const ZERO: Self = VecN([0; N]);
+
+    pub fn splat(value: i64) -> Self {
+        VecN([value; N])
+    }
+
+    /// Vector of length 1 along `axis`.
+    pub fn unit(axis: usize) -> Self {
+        let mut v = Self::ZERO;
+        v.0[axis] = 1;
+        v
+    }
+
+    fn zip_with(self, rhs: Self, f: impl Fn(i64, i64) -> i64) -> Self {
+        VecN(std::array::from_fn(|i| f(self.0[i], rhs.0[i])))
+    }
+
+    fn map(self, f: impl Fn(i64) -> i64) -> Self {
+        VecN(self.0.map(f))
+    }
+
+    /// Componentwise minimum.
+    pub fn component_min(self, rhs: Self) -> Self {
+        self.zip_with(rhs, i64::min)
+    }
+
+    /// Componentwise maximum.
+    pub fn component_max(self, rhs: Self) -> Self {
+        self.zip_with(rhs, i64::max)
+    }
+
+    pub fn manhattan_norm(&self) -> i64 {
+        self.0.iter().map(|c| c.abs()).sum()
+    }
+
+    pub fn manhattan_distance(&self, other: Self) -> i64 {
+        (*self - other).manhattan_norm()
+    }
+
+    pub fn chebyshev_norm(&self) -> i64 {
+        self.0.iter().map(|c| c.abs()).max().unwrap_or(0)
+    }
+
+    pub fn chebyshev_distance(&self, other: Self) -> i64 {
+        (*self - other).chebyshev_norm()
+    }
+
+    pub fn square_norm(&self) -> i64 {
+        self.0.iter().map(|c| c * c).sum()
+    }
+
+    /// The `2 * N` positions sharing a face (6 in 3D).
+    pub fn orthogonal_adjacent_iter(&self) -> impl Iterator<Item = Self> {
+        let pos = *self;
+        (0..N).flat_map(move |axis| [pos - Self::unit(axis), pos + Self::unit(axis)])
+    }
+
+    /// The `3^N - 1` positions sharing at least a corner (26 in 3D).
+    pub fn all_adjacent_iter(&self) -> impl Iterator<Item = Self> {
+        let pos = *self;
+        (0..3usize.pow(N as u32))
+            .map(|mut i| {
+                VecN(std::array::from_fn(|_| {
+                    let offset = (i % 3) as i64 - 1;
+                    i /= 3;
+                    offset
+                }))
+            })
+            .filter(|offset| *offset != Self::ZERO)
+            .map(move |offset| pos + offset)
+    }
+
+    /// Smallest and largest corners of the box containing all `positions`,
+    /// both included.
+    pub fn bounding_box(positions: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+        positions.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                Some((min, max)) => (p.component_min(min), p.component_max(max)),
+                None => (p, p),
+            })
+        })
+    }
+}
+
+impl From<Vec2> for VecN<2> {
+    fn from(v: Vec2) -> Self {
+        VecN([v.x, v.y])
+    }
+}
+
+impl From<VecN<2>> for Vec2 {
+    fn from(v: VecN<2>) -> Self {
+        Vec2 {
+            x: v.0[0],
+            y: v.0[1],
+        }
+    }
+}
+
+impl<const N: usize> std::ops::Index<usize> for VecN<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> std::ops::IndexMut<usize> for VecN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> std::ops::Add for VecN<N> {
+    type Output = VecN<N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> std::ops::Add<i64> for VecN<N> {
+    type Output = VecN<N>;
+
+    fn add(self, rhs: i64) -> Self::Output {
+        self.map(|a| a + rhs)
+    }
+}
+
+impl<const N: usize> std::ops::Sub for VecN<N> {
+    type Output = VecN<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> std::ops::Sub<i64> for VecN<N> {
+    type Output = VecN<N>;
+
+    fn sub(self, rhs: i64) -> Self::Output {
+        self.map(|a| a - rhs)
+    }
+}
+
+impl<const N: usize> std::ops::Sub<VecN<N>> for i64 {
+    type Output = VecN<N>;
+
+    fn sub(self, rhs: VecN<N>) -> Self::Output {
+        rhs.map(|a| self - a)
+    }
+}
+
+impl<const N: usize> std::ops::Mul for VecN<N> {
+    type Output = VecN<N>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a * b)
+    }
+}
+
+impl<const N: usize> std::ops::Mul<i64> for VecN<N> {
+    type Output = VecN<N>;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<const N: usize> std::ops::Mul<VecN<N>> for i64 {
+    type Output = VecN<N>;
+
+    fn mul(self, rhs: VecN<N>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<const N: usize> std::ops::Div<i64> for VecN<N> {
+    type Output = VecN<N>;
+
+    fn div(self, rhs: i64) -> Self::Output {
+        self.map(|a| a / rhs)
+    }
+}
+
+impl<const N: usize> std::ops::Neg for VecN<N> {
+    type Output = VecN<N>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
+    }
+}
+
+impl<const N: usize> std::iter::Sum for VecN<N> {
+    fn sum<I: Iterator<Item = VecN<N>>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, v| acc + v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_bounds() {
+        let (a, b) = (vec3(1, 5, -2), vec3(3, 0, -2));
+        assert_eq!(a.component_min(b), vec3(1, 0, -2));
+        assert_eq!(a.component_max(b), vec3(3, 5, -2));
+        // `Ord` still compares lexicographically.
+        assert_eq!(a.max(b), b);
+        assert_eq!(
+            Vec3::bounding_box([a, b, vec3(0, 2, 7)]),
+            Some((vec3(0, 0, -2), vec3(3, 5, 7)))
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        assert_eq!(Vec3::ZERO.orthogonal_adjacent_iter().count(), 6);
+        assert_eq!(Vec3::ZERO.all_adjacent_iter().count(), 26);
+        assert!(Vec3::ZERO
+            .all_adjacent_iter()
+            .all(|p| p.chebyshev_norm() == 1));
+    }
+}
@@ -0,0 +1,351 @@
+use super::{vec2, Map, Vec2};
+
+/// Borrowed, possibly transformed, rectangle of a [`Map`]. Transformations
+/// only change how positions are mapped to the underlying map, nothing is
+/// copied until [`MapView::to_map`].
+#[derive(Debug)]
+pub struct MapView<'a, T> {
+    map: &'a Map<T>,
+    /// Underlying position of the view top left cell.
+    origin: Vec2,
+    /// Underlying moves for one step right and one step down in the view.
+    x_axis: Vec2,
+    y_axis: Vec2,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for MapView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MapView<'_, T> {}
+
+impl<T> Map<T> {
+    pub fn view(&self) -> MapView<'_, T> {
+        MapView {
+            map: self,
+            origin: Vec2::default(),
+            x_axis: Vec2::EAST,
+            y_axis: Vec2::SOUTH,
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    pub fn transposed(&self) -> MapView<'_, T> {
+        self.view().transposed()
+    }
+
+    pub fn rotated_right(&self) -> MapView<'_, T> {
+        self.view().rotated_right()
+    }
+
+    pub fn rotated_left(&self) -> MapView<'_, T> {
+        self.view().rotated_left()
+    }
+
+    pub fn rotated_180(&self) -> MapView<'_, T> {
+        self.view().rotated_180()
+    }
+
+    pub fn flipped_horizontally(&self) -> MapView<'_, T> {
+        self.view().flipped_horizontally()
+    }
+
+    pub fn flipped_vertically(&self) -> MapView<'_, T> {
+        self.view().flipped_vertically()
+    }
+
+    pub fn sub_view(&self, top_left: Vec2, width: usize, height: usize) -> MapView<'_, T> {
+        self.view().sub_view(top_left, width, height)
+    }
+}
+
+impl<'a, T> MapView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> Vec2 {
+        vec2(self.width as i64, self.height as i64)
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    /// Position in the underlying map of the view position `pos`.
+    pub fn map_pos(&self, pos: Vec2) -> Vec2 {
+        self.origin + self.x_axis * pos.x + self.y_axis * pos.y
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<&'a T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        self.map.get(self.map_pos(pos))
+    }
+
+    /// Swaps rows and columns.
+    pub fn transposed(self) -> Self {
+        MapView {
+            x_axis: self.y_axis,
+            y_axis: self.x_axis,
+            width: self.height,
+            height: self.width,
+            ..self
+        }
+    }
+
+    /// Mirrors left and right.
+    pub fn flipped_horizontally(self) -> Self {
+        MapView {
+            origin: self.map_pos(vec2(self.width as i64 - 1, 0)),
+            x_axis: self.x_axis * -1,
+            ..self
+        }
+    }
+
+    /// Mirrors top and bottom.
+    pub fn flipped_vertically(self) -> Self {
+        MapView {
+            origin: self.map_pos(vec2(0, self.height as i64 - 1)),
+            y_axis: self.y_axis * -1,
+            ..self
+        }
+    }
+
+    /// Quarter turn clockwise.
+    pub fn rotated_right(self) -> Self {
+        self.transposed().flipped_horizontally()
+    }
+
+    /// Quarter turn counterclockwise.
+    pub fn rotated_left(self) -> Self {
+        self.transposed().flipped_vertically()
+    }
+
+    pub fn rotated_180(self) -> Self {
+        self.flipped_horizontally().flipped_vertically()
+    }
+
+    /// Rectangle of the view starting at `top_left`, clamped to the view.
+    pub fn sub_view(self, top_left: Vec2, width: usize, height: usize) -> Self {
+        let x = top_left.x.clamp(0, self.width as i64);
+        let y = top_left.y.clamp(0, self.height as i64);
+        MapView {
+            origin: self.map_pos(vec2(x, y)),
+            width: width.min(self.width - x as usize),
+            height: height.min(self.height - y as usize),
+            ..self
+        }
+    }
+
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator {
+        assert!(
+            y < self.height,
+            "row {y} out of view of height {}",
+            self.height
+        );
+        let view = *self;
+        (0..self.width).map(move |x| view.get(vec2(x as i64, y as i64)).unwrap())
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &'a T>> {
+        let view = *self;
+        (0..self.height).map(move |y| view.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator {
+        self.transposed().row(x)
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &'a T>> {
+        self.transposed().rows()
+    }
+
+    /// Cells in row major order, with their position in the view.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &'a T)> {
+        let view = *self;
+        (0..self.height as i64).flat_map(move |y| {
+            (0..view.width as i64).map(move |x| (vec2(x, y), view.get(vec2(x, y)).unwrap()))
+        })
+    }
+
+    pub fn iter_from_point(
+        &self,
+        start: Vec2,
+        direction: Vec2,
+    ) -> impl Iterator<Item = (Vec2, &'a T)> {
+        let view = *self;
+        std::iter::successors(Some(start), move |p| Some(*p + direction))
+            .map_while(move |p| view.get(p).map(|v| (p, v)))
+    }
+
+    pub fn four_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + 'a {
+        let view = *self;
+        pos.four_adjacent_iter().filter(move |&p| view.contains(p))
+    }
+
+    pub fn eight_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + 'a {
+        let view = *self;
+        Vec2::directions()
+            .into_iter()
+            .map(move |d| pos + d)
+            .filter(move |&p| view.contains(p))
+    }
+
+    pub fn to_map(&self) -> Map<T>
+    where
+        T: Clone,
+    {
+        Map::from_vec(self.width, self.iter().map(|(_, v)| v.clone()).collect())
+            .expect("view cells fill its width")
+    }
+}
+
+impl<T> std::ops::Index<Vec2> for MapView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        match self.get(pos) {
+            Some(v) => v,
+            None => panic!("{pos:?} out of view of size {:?}", self.size()),
+        }
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for MapView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for l in self.rows() {
+            for c in l {
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 123
+    // 456
+    fn sample() -> Map<u8> {
+        Map::new(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    fn cells(view: MapView<'_, u8>) -> Vec<Vec<u8>> {
+        view.rows().map(|r| r.copied().collect()).collect()
+    }
+
+    #[test]
+    fn transforms() {
+        let map = sample();
+        assert_eq!(cells(map.view()), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(
+            cells(map.transposed()),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+        assert_eq!(
+            cells(map.rotated_right()),
+            vec![vec![4, 1], vec![5, 2], vec![6, 3]]
+        );
+        assert_eq!(
+            cells(map.rotated_left()),
+            vec![vec![3, 6], vec![2, 5], vec![1, 4]]
+        );
+        assert_eq!(cells(map.rotated_180()), vec![vec![6, 5, 4], vec![3, 2, 1]]);
+        assert_eq!(
+            cells(map.flipped_horizontally()),
+            vec![vec![3, 2, 1], vec![6, 5, 4]]
+        );
+        assert_eq!(
+            cells(map.flipped_vertically()),
+            vec![vec![4, 5, 6], vec![1, 2, 3]]
+        );
+        assert_eq!(map.rotated_right().size(), vec2(2, 3));
+        assert_eq!(map.rotated_right().map_pos(vec2(0, 0)), vec2(0, 1));
+        assert_eq!(map.rotated_left()[vec2(1, 2)], 4);
+        assert_eq!(map.rotated_left().get(vec2(2, 0)), None);
+    }
+
+    #[test]
+    fn compositions() {
+        fn turned(view: MapView<'_, u8>, quarters: usize) -> MapView<'_, u8> {
+            (0..quarters).fold(view, |v, _| v.rotated_right())
+        }
+
+        let map = sample();
+        let view = map.view();
+        assert_eq!(cells(turned(view, 4)), cells(view));
+        assert_eq!(cells(turned(view, 2)), cells(map.rotated_180()));
+        assert_eq!(cells(turned(view, 3)), cells(map.rotated_left()));
+        assert_eq!(cells(view.rotated_left().rotated_right()), cells(view));
+        assert_eq!(cells(view.transposed().transposed()), cells(view));
+        assert_eq!(
+            cells(view.flipped_horizontally().flipped_vertically()),
+            cells(map.rotated_180())
+        );
+        assert_eq!(turned(view, 4).to_map(), map);
+    }
+
+    #[test]
+    fn sub_views() {
+        let map = Map::new(
+            (0..4)
+                .map(|y| (0..5).map(|x| y * 10 + x).collect())
+                .collect(),
+        );
+        let sub = map.sub_view(vec2(1, 1), 3, 2);
+        assert_eq!(cells(sub), vec![vec![11, 12, 13], vec![21, 22, 23]]);
+        assert_eq!(sub.map_pos(vec2(0, 0)), vec2(1, 1));
+        assert_eq!(sub.column(2).copied().collect::<Vec<_>>(), vec![13, 23]);
+        assert_eq!(
+            cells(sub.rotated_right()),
+            vec![vec![21, 11], vec![22, 12], vec![23, 13]]
+        );
+
+        // Sub-views of transformed views follow the transformed axes.
+        let sub = map.rotated_180().sub_view(vec2(0, 0), 2, 2);
+        assert_eq!(cells(sub), vec![vec![34, 33], vec![24, 23]]);
+
+        // Rectangles going past the view are clamped.
+        let sub = map.sub_view(vec2(3, 2), 10, 10);
+        assert_eq!(cells(sub), vec![vec![23, 24], vec![33, 34]]);
+        let sub = map.sub_view(vec2(7, 1), 2, 2);
+        assert_eq!(sub.size(), vec2(0, 2));
+        assert_eq!(sub.iter().count(), 0);
+        assert_eq!(sub.get(vec2(0, 0)), None);
+
+        let mut neighbors = map
+            .sub_view(vec2(1, 1), 2, 2)
+            .four_adjacent_pos_iter(vec2(0, 0))
+            .collect::<Vec<_>>();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![vec2(0, 1), vec2(1, 0)]);
+    }
+
+    #[test]
+    fn display_and_walks() {
+        let map = sample();
+        assert_eq!(map.rotated_right().to_string(), "41\n52\n63\n");
+        assert_eq!(
+            map.transposed()
+                .iter_from_point(vec2(1, 0), Vec2::SOUTH)
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        assert_eq!(map.rotated_left().to_map().size(), vec2(2, 3));
+    }
+}
@@ -0,0 +1,251 @@
+use super::{Map, Vec2};
+
+impl Vec2 {
+    /// Position wrapped in a grid of size `size`, see [`i64::rem_euclid`].
+    pub fn rem_euclid(self, size: Vec2) -> Self {
+        Vec2 {
+            x: self.x.rem_euclid(size.x),
+            y: self.y.rem_euclid(size.y),
+        }
+    }
+
+    /// Coordinates of the grid of size `size` containing the position, when
+    /// the plane is tiled with it.
+    pub fn div_euclid(self, size: Vec2) -> Self {
+        Vec2 {
+            x: self.x.div_euclid(size.x),
+            y: self.y.div_euclid(size.y),
+        }
+    }
+}
+
+impl<T> Map<T> {
+    /// View of the map as a torus, leaving one side coming back from the
+    /// opposite one.
+    pub fn wrapping(&self) -> Wrapping<'_, T> {
+        Wrapping { map: self }
+    }
+
+    /// View of the map repeated infinitely in every direction.
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        Tiled { map: self }
+    }
+}
+
+/// See [`Map::wrapping`]. Returned positions always lie in the underlying map.
+#[derive(Debug, Clone, Copy)]
+pub struct Wrapping<'a, T> {
+    map: &'a Map<T>,
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    /// Panics if the map has no cell.
+    pub fn wrap(&self, pos: Vec2) -> Vec2 {
+        assert_has_cells(self.map);
+        pos.rem_euclid(self.map.size())
+    }
+
+    /// Position reached moving by `delta` from `pos`.
+    pub fn step(&self, pos: Vec2, delta: Vec2) -> Vec2 {
+        self.wrap(pos + delta)
+    }
+
+    /// `None` only if the map has no cell.
+    pub fn get(&self, pos: Vec2) -> Option<&'a T> {
+        if has_no_cell(self.map) {
+            return None;
+        }
+        self.map.get(self.wrap(pos))
+    }
+
+    /// Empty if the map has no cell.
+    pub fn four_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        let neighbors = (!has_no_cell(self.map)).then(|| pos.four_adjacent_iter());
+        neighbors.into_iter().flatten().map(|p| self.wrap(p))
+    }
+
+    /// Empty if the map has no cell.
+    pub fn eight_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        let neighbors = (!has_no_cell(self.map)).then(Vec2::directions);
+        neighbors
+            .into_iter()
+            .flatten()
+            .map(move |d| self.wrap(pos + d))
+    }
+
+    /// Never ending walk from `start`, going round the map.
+    pub fn iter_from_point(
+        &self,
+        start: Vec2,
+        direction: Vec2,
+    ) -> impl Iterator<Item = (Vec2, &'a T)> + '_ {
+        let start = (!has_no_cell(self.map)).then(|| self.wrap(start));
+        std::iter::successors(start, move |p| Some(self.step(*p, direction)))
+            .map_while(|p| self.get(p).map(|v| (p, v)))
+    }
+}
+
+/// Panics if the map has no cell.
+impl<T> std::ops::Index<Vec2> for Wrapping<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        &self.map[self.wrap(pos)]
+    }
+}
+
+/// See [`Map::tiled`]. Returned positions are left as is, [`Tiled::tile_of`]
+/// telling which copy of the map they are in.
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+    map: &'a Map<T>,
+}
+
+impl<'a, T> Tiled<'a, T> {
+    /// Panics if the map has no cell.
+    pub fn tile_of(&self, pos: Vec2) -> Vec2 {
+        assert_has_cells(self.map);
+        pos.div_euclid(self.map.size())
+    }
+
+    /// Position in the underlying map, panics if it has no cell.
+    pub fn base_pos(&self, pos: Vec2) -> Vec2 {
+        assert_has_cells(self.map);
+        pos.rem_euclid(self.map.size())
+    }
+
+    /// `None` only if the map has no cell.
+    pub fn get(&self, pos: Vec2) -> Option<&'a T> {
+        if has_no_cell(self.map) {
+            return None;
+        }
+        self.map.get(self.base_pos(pos))
+    }
+
+    /// Empty if the map has no cell.
+    pub fn four_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> {
+        let neighbors = (!has_no_cell(self.map)).then(|| pos.four_adjacent_iter());
+        neighbors.into_iter().flatten()
+    }
+
+    /// Empty if the map has no cell.
+    pub fn eight_adjacent_pos_iter(&self, pos: Vec2) -> impl Iterator<Item = Vec2> {
+        let neighbors = (!has_no_cell(self.map)).then(Vec2::directions);
+        neighbors.into_iter().flatten().map(move |d| pos + d)
+    }
+
+    /// Never ending walk from `start`, crossing tiles.
+    pub fn iter_from_point(
+        &self,
+        start: Vec2,
+        direction: Vec2,
+    ) -> impl Iterator<Item = (Vec2, &'a T)> + '_ {
+        std::iter::successors(Some(start), move |p| Some(*p + direction))
+            .map_while(|p| self.get(p).map(|v| (p, v)))
+    }
+}
+
+fn has_no_cell<T>(map: &Map<T>) -> bool {
+    map.width() == 0 || map.height() == 0
+}
+
+fn assert_has_cells<T>(map: &Map<T>) {
+    assert!(
+        !has_no_cell(map),
+        "map of size {:?} has no cell to wrap around",
+        map.size()
+    );
+}
+
+/// Panics if the map has no cell.
+impl<T> std::ops::Index<Vec2> for Tiled<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        &self.map[self.base_pos(pos)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::vec2;
+
+    #[test]
+    fn wraps_around() {
+        let map = Map::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let wrapping = map.wrapping();
+        assert_eq!(wrapping.wrap(vec2(-1, 2)), vec2(2, 0));
+        assert_eq!(wrapping[vec2(4, -1)], 5);
+        assert_eq!(map.tiled().tile_of(vec2(-1, 2)), vec2(-1, 1));
+        assert_eq!(map.tiled().get(vec2(-3, -2)), Some(&1));
+    }
+
+    #[test]
+    fn maps_without_cells() {
+        let no_width = Map::<u8>::new(vec![vec![], vec![]]);
+        let no_height = Map::<u8>::filled(3, 0, 0);
+        for map in [no_width, no_height, Map::filled(0, 0, 0)] {
+            assert_eq!(map.wrapping().get(vec2(1, 1)), None);
+            assert_eq!(map.tiled().get(vec2(1, 1)), None);
+            assert_eq!(
+                map.wrapping()
+                    .iter_from_point(vec2(0, 0), Vec2::EAST)
+                    .count(),
+                0
+            );
+            assert_eq!(
+                map.tiled().iter_from_point(vec2(0, 0), Vec2::EAST).count(),
+                0
+            );
+            assert_eq!(map.wrapping().four_adjacent_pos_iter(vec2(0, 0)).count(), 0);
+            assert_eq!(
+                map.wrapping().eight_adjacent_pos_iter(vec2(0, 0)).count(),
+                0
+            );
+            assert_eq!(map.tiled().four_adjacent_pos_iter(vec2(0, 0)).count(), 0);
+            assert_eq!(map.tiled().eight_adjacent_pos_iter(vec2(0, 0)).count(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "has no cell to wrap around")]
+    fn index_wrapping_without_cells() {
+        let map = Map::<u8>::filled(3, 0, 0);
+        let _ = map.wrapping()[vec2(1, 1)];
+    }
+
+    #[test]
+    #[should_panic(expected = "has no cell to wrap around")]
+    fn index_tiled_without_cells() {
+        let map = Map::<u8>::new(vec![vec![]]);
+        let _ = map.tiled()[vec2(0, 0)];
+    }
+
+    #[test]
+    fn neighbors() {
+        let map = Map::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mut wrapped = map
+            .wrapping()
+            .four_adjacent_pos_iter(vec2(0, 0))
+            .collect::<Vec<_>>();
+        wrapped.sort();
+        assert_eq!(
+            wrapped,
+            vec![vec2(0, 1), vec2(0, 1), vec2(1, 0), vec2(2, 0)]
+        );
+        assert!(map
+            .wrapping()
+            .eight_adjacent_pos_iter(vec2(2, 1))
+            .all(|p| map.contains(p)));
+        assert_eq!(map.tiled().eight_adjacent_pos_iter(vec2(0, 0)).count(), 8);
+        assert_eq!(
+            map.wrapping()
+                .iter_from_point(vec2(-1, 0), Vec2::EAST)
+                .take(5)
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![3, 1, 2, 3, 1]
+        );
+    }
+}
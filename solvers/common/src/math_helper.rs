@@ -1,41 +1,82 @@
+mod digits;
+mod linear;
+mod polynomial;
+
+pub use digits::*;
+pub use linear::*;
+pub use polynomial::*;
+
 use std::cmp::Ordering;
 
+/// Number of digits of `n`, ignoring its sign, see [`Digits`].
 pub fn num_digits_in_base_10(n: i64) -> u32 {
-    if n == 0 {
-        // Special case: 0 has exactly 1 digit in any base.
-        1
-    } else {
-        n.ilog10() + 1
-    }
+    n.num_digits()
+}
+
+/// Primitive integers, with the few operations searches need to never
+/// overflow.
+pub trait PrimInt: Copy + Ord + std::fmt::Debug {
+    const ONE: Self;
+    const MAX: Self;
+
+    /// Middle of `low..high`, rounded down, `low` being at most `high`.
+    fn mid(low: Self, high: Self) -> Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
 }
 
-pub fn binary_search(
-    range: std::ops::Range<usize>,
-    pred: impl Fn(usize) -> Ordering,
-) -> Result<usize, usize> {
+macro_rules! impl_prim_int {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl PrimInt for $t {
+                const ONE: Self = 1;
+                const MAX: Self = <$t>::MAX;
+
+                fn mid(low: Self, high: Self) -> Self {
+                    low.wrapping_add((high.wrapping_sub(low) as $u / 2) as $t)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_prim_int!(
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+);
+
+/// Binary search over `range`, `pred` telling how each value compares to the
+/// searched one. On failure, returns where it would be inserted.
+pub fn binary_search<T: PrimInt>(
+    range: std::ops::Range<T>,
+    mut pred: impl FnMut(T) -> Ordering,
+) -> Result<T, T> {
     let mut low = range.start;
-    let mut high = range.end - 1;
+    let mut high = range.end;
 
-    let mut cmp = None;
-    while low <= high {
-        let mid = (low + high) / 2;
-        let c = pred(mid);
-        cmp = Some(c);
-        match c {
-            Ordering::Less => low = mid + 1,
-            Ordering::Greater => high = mid - 1,
-            Ordering::Equal => break,
+    while low < high {
+        let mid = T::mid(low, high);
+        match pred(mid) {
+            // `mid < high`, so this cannot overflow.
+            Ordering::Less => low = mid.checked_add(T::ONE).unwrap(),
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Ok(mid),
         }
     }
 
-    cmp.ok_or(0).and_then(|c| match c {
-        Ordering::Less => Err(low),
-        Ordering::Equal => Ok(low),
-        Ordering::Greater => Err(high + 1),
-    })
+    Err(low)
 }
 
-pub fn partition_point(range: std::ops::Range<usize>, pred: impl Fn(usize) -> bool) -> usize {
+/// First value of `range` for which `pred` is false, `range.end` if none,
+/// `pred` having to be true then false over the range.
+pub fn partition_point<T: PrimInt>(
+    range: std::ops::Range<T>,
+    mut pred: impl FnMut(T) -> bool,
+) -> T {
     binary_search(range, |i| {
         if pred(i) {
             Ordering::Less
@@ -45,3 +86,276 @@ pub fn partition_point(range: std::ops::Range<usize>, pred: impl Fn(usize) -> bo
     })
     .unwrap_or_else(|i| i)
 }
+
+/// Same as [`partition_point`] from `start` with no known upper bound,
+/// probing exponentially growing distances first. Returns `None` if `pred`
+/// holds up to the type maximum.
+pub fn gallop<T: PrimInt>(start: T, mut pred: impl FnMut(T) -> bool) -> Option<T> {
+    if !pred(start) {
+        return Some(start);
+    }
+
+    let mut low = start;
+    let mut step = T::ONE;
+    loop {
+        let Some(probe) = low.checked_add(step) else {
+            if pred(T::MAX) {
+                return None;
+            }
+            return Some(partition_point(low..T::MAX, pred));
+        };
+        if !pred(probe) {
+            return Some(partition_point(low..probe, pred));
+        }
+        low = probe;
+        step = step.checked_add(step).unwrap_or(T::MAX);
+    }
+}
+
+/// Non-negative GCD, panics for `gcd(i64::MIN, 0)` and `gcd(i64::MIN,
+/// i64::MIN)` which are `2^63`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    i64::try_from(extended_gcd(a, b).0).expect("GCD overflows an i64")
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, `g` being the
+/// non-negative GCD of `a` and `b`. Results are `i128` as `g` may be `2^63`.
+pub fn extended_gcd(a: i64, b: i64) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    (old_r, old_x, old_y)
+}
+
+/// `a * b mod m` in `0..m`, without overflowing.
+pub fn mul_mod(a: i64, b: i64, m: i64) -> i64 {
+    (a as i128 * b as i128).rem_euclid(m as i128) as i64
+}
+
+/// `base^exp mod m` in `0..m`.
+pub fn pow_mod(base: i64, mut exp: u64, m: i64) -> i64 {
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// `x` in `0..m` such that `a * x mod m == 1`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m as i128) as i64)
+}
+
+/// Solves `x mod modulus == residue` for every `(residue, modulus)` pair,
+/// moduli not having to be coprime. Returns `(x, lcm of moduli)` with `x` in
+/// `0..lcm`, or `None` if the congruences are inconsistent.
+///
+/// Panics if the moduli LCM does not fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0i64, 1i64), |(r1, m1), (r2, m2)| {
+            let (r1, m1) = (r1 as i128, m1 as i128);
+            let m2 = m2 as i128;
+            let r2 = (r2 as i128).rem_euclid(m2);
+            let (g, p, _) = extended_gcd(m1 as i64, m2 as i64);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+
+            let lcm = m1 / g * m2;
+            i64::try_from(lcm).expect("moduli LCM overflows an i64");
+            // x = r1 + m1 * k, with k = (r2 - r1) / g * p mod (m2 / g)
+            let k = ((r2 - r1) / g * p).rem_euclid(m2 / g);
+            let x = (r1 + m1 * k).rem_euclid(lcm);
+            Some((x as i64, lcm as i64))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Small xorshift generator, tests only need reproducible noise.
+    fn random(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    #[test]
+    fn searches_match_slice_partition_point() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2000 {
+            let len = (random(&mut seed) % 40) as usize;
+            let mut values = (0..len)
+                .map(|_| (random(&mut seed) % 20) as i32)
+                .collect::<Vec<_>>();
+            values.sort();
+
+            for target in -1..=21 {
+                let expected = values.partition_point(|&v| v < target);
+                assert_eq!(partition_point(0..len, |i| values[i] < target), expected);
+                match binary_search(0..len, |i| values[i].cmp(&target)) {
+                    Ok(i) => assert_eq!(values[i], target),
+                    Err(i) => {
+                        assert_eq!(i, expected);
+                        assert!(!values.contains(&target));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn searches_on_empty_and_tiny_ranges() {
+        assert_eq!(partition_point(5..5, |_: i32| true), 5);
+        assert_eq!(binary_search(3u8..3, |_| Ordering::Less), Err(3));
+        let (start, end) = (7i64, 2);
+        assert_eq!(binary_search(start..end, |_| Ordering::Less), Err(7));
+        // `mid` is 0.
+        assert_eq!(partition_point(0u8..1, |_| false), 0);
+        assert_eq!(partition_point(0u8..1, |_| true), 1);
+        assert_eq!(binary_search(0u64..1, |i| i.cmp(&0)), Ok(0));
+        assert_eq!(binary_search(0u64..2, |i| i.cmp(&1)), Ok(1));
+    }
+
+    #[test]
+    fn searches_on_full_width_ranges() {
+        for target in [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1] {
+            assert_eq!(partition_point(i64::MIN..i64::MAX, |x| x < target), target);
+            assert_eq!(
+                binary_search(i64::MIN..i64::MAX, |x| x.cmp(&target)),
+                Ok(target)
+            );
+        }
+        assert_eq!(
+            partition_point(i64::MIN..i64::MAX, |x| x < i64::MAX),
+            i64::MAX
+        );
+
+        for target in [0, 1, u64::MAX / 2, u64::MAX - 1] {
+            assert_eq!(partition_point(0..u64::MAX, |x| x < target), target);
+            assert_eq!(binary_search(0..u64::MAX, |x| x.cmp(&target)), Ok(target));
+        }
+        assert_eq!(
+            binary_search(0..u64::MAX, |_| Ordering::Less),
+            Err(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn gallop_up_to_the_type_maximum() {
+        for start in i8::MIN..=i8::MAX {
+            for threshold in start..=i8::MAX {
+                assert_eq!(gallop(start, |x| x < threshold), Some(threshold));
+            }
+            assert_eq!(gallop(start, |_| true), None);
+        }
+        for start in [0, 1, 200, 254, 255] {
+            for threshold in start..=u8::MAX {
+                assert_eq!(gallop(start, |x| x < threshold), Some(threshold));
+            }
+        }
+
+        assert_eq!(gallop(0, |x| x < u64::MAX - 3), Some(u64::MAX - 3));
+        assert_eq!(gallop(0, |x| x < u64::MAX), Some(u64::MAX));
+        assert_eq!(gallop(0u64, |_| true), None);
+        assert_eq!(gallop(i64::MIN, |x| x < i64::MAX - 1), Some(i64::MAX - 1));
+        assert_eq!(gallop(i64::MIN, |x| x < i64::MAX), Some(i64::MAX));
+    }
+
+    #[test]
+    fn extended_gcd_bezout() {
+        let mut seed = 0x9e37_79b9_7f4a_7c15;
+        let edges = [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX];
+        let pairs = edges
+            .iter()
+            .flat_map(|&a| edges.iter().map(move |&b| (a, b)))
+            .chain((0..1000).map(|_| (random(&mut seed) as i64, random(&mut seed) as i64 >> 20)));
+        for (a, b) in pairs {
+            let (g, x, y) = extended_gcd(a, b);
+            assert!(g >= 0);
+            assert_eq!(a as i128 * x + b as i128 * y, g, "{a} {b}");
+            if g != 0 {
+                assert_eq!(a as i128 % g, 0);
+                assert_eq!(b as i128 % g, 0);
+            }
+        }
+        assert_eq!(extended_gcd(i64::MIN, 0).0, 1 << 63);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(-4, 0), 4);
+        assert_eq!(lcm(4, -6), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "GCD overflows")]
+    fn gcd_of_min_overflows() {
+        gcd(i64::MIN, i64::MIN);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(-3, 7), Some(2));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(0, 5), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(pow_mod(2, 62, i64::MAX), 1 << 62);
+        assert_eq!(pow_mod(3, 0, 1), 0);
+        assert_eq!(mul_mod(i64::MAX, i64::MAX, 1_000_000_007), 737_564_071);
+    }
+
+    #[test]
+    fn crt_with_non_coprime_moduli() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 4), (5, 6), (5, 10)]), Some((35, 60)));
+        assert_eq!(crt([]), Some((0, 1)));
+
+        // Brute force over small moduli.
+        for m1 in 1..13 {
+            for m2 in 1..13 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let lcm = lcm(m1, m2);
+                        let expected = (0..lcm).find(|x| x % m1 == r1 && x % m2 == r2);
+                        assert_eq!(crt([(r1, m1), (r2, m2)]), expected.map(|x| (x, lcm)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "moduli LCM overflows")]
+    fn crt_lcm_overflow() {
+        crt([(0, i64::MAX), (0, i64::MAX - 1)]);
+    }
+}
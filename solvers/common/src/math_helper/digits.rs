@@ -0,0 +1,238 @@
+/// Digit manipulations, in base 10 unless stated otherwise. Negative numbers
+/// work on their absolute value and keep their sign.
+pub trait Digits: Copy {
+    /// Whether the number is negative, and its absolute value.
+    fn to_sign_magnitude(self) -> (bool, u128);
+
+    /// Inverse of [`Digits::to_sign_magnitude`], `None` when out of range.
+    fn from_sign_magnitude(negative: bool, magnitude: u128) -> Option<Self>;
+
+    fn num_digits(self) -> u32 {
+        self.num_digits_in_base(10)
+    }
+
+    /// `0` has one digit.
+    fn num_digits_in_base(self, base: u32) -> u32 {
+        assert!(base >= 2, "invalid base {base}");
+        match self.to_sign_magnitude() {
+            (_, 0) => 1,
+            (_, m) => m.ilog(base as u128) + 1,
+        }
+    }
+
+    fn digits(self) -> impl DoubleEndedIterator<Item = u32> + ExactSizeIterator {
+        self.digits_in_base(10)
+    }
+
+    /// Most significant digit first.
+    fn digits_in_base(self, base: u32) -> impl DoubleEndedIterator<Item = u32> + ExactSizeIterator {
+        let (_, m) = self.to_sign_magnitude();
+        let base = base as u128;
+        (0..self.num_digits_in_base(base as u32))
+            .rev()
+            .map(move |i| (m / base.pow(i) % base) as u32)
+    }
+
+    /// Panics on overflow.
+    fn from_digits(digits: impl IntoIterator<Item = u32>) -> Self {
+        Self::checked_from_digits(digits).expect("number overflows")
+    }
+
+    fn checked_from_digits(digits: impl IntoIterator<Item = u32>) -> Option<Self> {
+        Self::checked_from_digits_in_base(digits, 10)
+    }
+
+    /// Panics on overflow.
+    fn from_digits_in_base(digits: impl IntoIterator<Item = u32>, base: u32) -> Self {
+        Self::checked_from_digits_in_base(digits, base).expect("number overflows")
+    }
+
+    /// Most significant digit first.
+    fn checked_from_digits_in_base(
+        digits: impl IntoIterator<Item = u32>,
+        base: u32,
+    ) -> Option<Self> {
+        assert!(base >= 2, "invalid base {base}");
+        let magnitude = digits.into_iter().try_fold(0u128, |acc, d| {
+            assert!(d < base, "invalid digit {d} in base {base}");
+            acc.checked_mul(base as u128)?.checked_add(d as u128)
+        })?;
+        Self::from_sign_magnitude(false, magnitude)
+    }
+
+    /// Digits in `base` up to 36, with a leading `-` for negative numbers.
+    fn to_string_in_base(self, base: u32) -> String {
+        assert!((2..=36).contains(&base), "invalid base {base}");
+        let (negative, _) = self.to_sign_magnitude();
+        let digits = self
+            .digits_in_base(base)
+            .map(|d| char::from_digit(d, base).unwrap());
+        negative.then_some('-').into_iter().chain(digits).collect()
+    }
+
+    /// Digits of `other` appended to the ones of `self`, `12.concat(345)`
+    /// being `12345`. The sign of `other` is ignored. Panics on overflow.
+    fn concat(self, other: Self) -> Self {
+        self.checked_concat(other).expect("concatenation overflows")
+    }
+
+    fn checked_concat(self, other: Self) -> Option<Self> {
+        let (negative, a) = self.to_sign_magnitude();
+        let (_, b) = other.to_sign_magnitude();
+        let shifted = a.checked_mul(10u128.checked_pow(other.num_digits())?)?;
+        Self::from_sign_magnitude(negative, shifted.checked_add(b)?)
+    }
+
+    /// The number made of the `index` leading digits and the one made of the
+    /// others, `1234.split_at_digit(1)` being `(1, 234)`. Both halves keep
+    /// the sign, like `/` and `%` do, so `-1234` gives `(-1, -234)` and
+    /// `head * 10^k + tail` is the original number.
+    fn split_at_digit(self, index: u32) -> (Self, Self) {
+        let (negative, m) = self.to_sign_magnitude();
+        let low_digits = self.num_digits().saturating_sub(index);
+        let power = 10u128.pow(low_digits);
+        (
+            Self::from_sign_magnitude(negative, m / power).unwrap(),
+            Self::from_sign_magnitude(negative, m % power).unwrap(),
+        )
+    }
+
+    /// Digits in reverse order, `120` giving `21`. Panics on overflow.
+    fn reverse_digits(self) -> Self {
+        self.checked_reverse_digits()
+            .expect("reversed number overflows")
+    }
+
+    fn checked_reverse_digits(self) -> Option<Self> {
+        let (negative, _) = self.to_sign_magnitude();
+        let magnitude = self
+            .digits()
+            .rev()
+            .try_fold(0u128, |acc, d| acc.checked_mul(10)?.checked_add(d as u128))?;
+        Self::from_sign_magnitude(negative, magnitude)
+    }
+}
+
+macro_rules! impl_digits {
+    (signed: $($t:ty),*; unsigned: $($u:ty),*) => {
+        $(
+            impl Digits for $t {
+                fn to_sign_magnitude(self) -> (bool, u128) {
+                    (self < 0, self.unsigned_abs() as u128)
+                }
+
+                fn from_sign_magnitude(negative: bool, magnitude: u128) -> Option<Self> {
+                    if negative {
+                        (0 as $t).checked_sub_unsigned(magnitude.try_into().ok()?)
+                    } else {
+                        magnitude.try_into().ok()
+                    }
+                }
+            }
+        )*
+        $(
+            impl Digits for $u {
+                fn to_sign_magnitude(self) -> (bool, u128) {
+                    (false, self as u128)
+                }
+
+                fn from_sign_magnitude(negative: bool, magnitude: u128) -> Option<Self> {
+                    match negative {
+                        true => (magnitude == 0).then_some(0),
+                        false => magnitude.try_into().ok(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_digits!(
+    signed: i8, i16, i32, i64, i128, isize;
+    unsigned: u8, u16, u32, u64, u128, usize
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_iterates_digits() {
+        assert_eq!(i64::MIN.num_digits(), 19);
+        assert_eq!(i128::MIN.num_digits(), 39);
+        assert_eq!(u128::MAX.num_digits(), 39);
+        assert_eq!((-1i8).num_digits(), 1);
+        assert_eq!(0u32.digits().collect::<Vec<_>>(), vec![0]);
+        assert_eq!((-907i32).digits().collect::<Vec<_>>(), vec![9, 0, 7]);
+        assert_eq!(255u8.digits_in_base(2).len(), 8);
+        assert_eq!(0u8.num_digits_in_base(2), 1);
+    }
+
+    #[test]
+    fn converts_between_bases() {
+        assert_eq!(u128::MAX.to_string_in_base(36), "f5lxx1zz5pnorynqglhzmsp33");
+        assert_eq!(i8::MIN.to_string_in_base(2), "-10000000");
+        assert_eq!(0i64.to_string_in_base(16), "0");
+        for n in -300i16..300 {
+            let s = n.to_string_in_base(7);
+            assert_eq!(i16::from_str_radix(&s, 7), Ok(n));
+        }
+
+        assert_eq!(u8::from_digits([2, 5, 5]), 255);
+        assert_eq!(u8::checked_from_digits([2, 5, 6]), None);
+        assert_eq!(i8::checked_from_digits([1, 2, 8]), None);
+        assert_eq!(u16::from_digits_in_base([1, 0, 1], 2), 5);
+        assert_eq!(u32::checked_from_digits([]), Some(0));
+    }
+
+    #[test]
+    fn concat_matches_text_concatenation() {
+        for a in i8::MIN..=i8::MAX {
+            for b in i8::MIN..=i8::MAX {
+                let expected = format!("{a}{}", b.unsigned_abs()).parse::<i8>().ok();
+                assert_eq!(a.checked_concat(b), expected, "{a} {b}");
+            }
+        }
+        assert_eq!(12i64.concat(345), 12345);
+        assert_eq!(25u8.checked_concat(5), Some(255));
+        assert_eq!(25u8.checked_concat(6), None);
+        assert_eq!(u128::MAX.checked_concat(1), None);
+        assert_eq!(i64::MIN.checked_concat(0), None);
+    }
+
+    #[test]
+    fn reverse_overflows() {
+        for n in i8::MIN..=i8::MAX {
+            let reversed = n
+                .unsigned_abs()
+                .to_string()
+                .chars()
+                .rev()
+                .collect::<String>();
+            let expected = reversed.parse::<i16>().unwrap() * n.signum() as i16;
+            assert_eq!(
+                n.checked_reverse_digits(),
+                i8::try_from(expected).ok(),
+                "{n}"
+            );
+        }
+        assert_eq!(121i8.checked_reverse_digits(), Some(121));
+        assert_eq!(127i8.checked_reverse_digits(), None);
+        assert_eq!(i8::MIN.checked_reverse_digits(), None);
+        assert_eq!((-120i8).reverse_digits(), -21);
+        assert_eq!(u128::MAX.checked_reverse_digits(), None);
+    }
+
+    #[test]
+    fn splits_at_digit() {
+        assert_eq!(1234u32.split_at_digit(0), (0, 1234));
+        assert_eq!(1234u32.split_at_digit(1), (1, 234));
+        assert_eq!(1234u32.split_at_digit(4), (1234, 0));
+        assert_eq!(1234u32.split_at_digit(9), (1234, 0));
+        assert_eq!((-1234i32).split_at_digit(1), (-1, -234));
+        assert_eq!((-1234i32).split_at_digit(0), (0, -1234));
+        assert_eq!((-1200i32).split_at_digit(2), (-12, 0));
+        assert_eq!(i8::MIN.split_at_digit(1), (-1, -28));
+        assert_eq!(i64::MIN.split_at_digit(19), (i64::MIN, 0));
+    }
+}
@@ -0,0 +1,190 @@
+use num::{rational::Ratio, One, Zero};
+
+/// Exact rational number.
+pub type Rational = Ratio<i128>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSolution {
+    Unique(Vec<Rational>),
+    None,
+    Infinite,
+}
+
+impl LinearSolution {
+    /// The unique solution, if it only has integer values.
+    pub fn integral(&self) -> Option<Vec<i128>> {
+        match self {
+            LinearSolution::Unique(values) => values
+                .iter()
+                .map(|v| v.is_integer().then(|| v.to_integer()))
+                .collect(),
+            LinearSolution::None | LinearSolution::Infinite => None,
+        }
+    }
+
+    pub fn is_integral(&self) -> bool {
+        self.integral().is_some()
+    }
+}
+
+/// Solves `coefficients * x = constants`, one row of coefficients per
+/// equation.
+pub fn solve_linear_system(coefficients: &[Vec<i64>], constants: &[i64]) -> LinearSolution {
+    let to_rationals = |row: &[i64]| row.iter().map(|&c| Rational::from(c as i128)).collect();
+    solve_rational_linear_system(
+        &coefficients
+            .iter()
+            .map(|r| to_rationals(r))
+            .collect::<Vec<_>>(),
+        &to_rationals(constants),
+    )
+}
+
+/// Same as [`solve_linear_system`], with Gaussian elimination over exact
+/// rationals.
+pub fn solve_rational_linear_system(
+    coefficients: &[Vec<Rational>],
+    constants: &[Rational],
+) -> LinearSolution {
+    assert_eq!(
+        coefficients.len(),
+        constants.len(),
+        "one constant is needed per equation"
+    );
+    let unknowns = coefficients.first().map_or(0, |r| r.len());
+    assert!(
+        coefficients.iter().all(|r| r.len() == unknowns),
+        "all equations must have the same number of coefficients"
+    );
+
+    // Augmented matrix, reduced to row echelon form.
+    let mut rows = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(r, c)| r.iter().copied().chain([*c]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut pivots = vec![];
+    for column in 0..unknowns {
+        let rank = pivots.len();
+        let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let factor = rows[rank][column];
+        for v in &mut rows[rank] {
+            *v /= factor;
+        }
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != rank && !row[column].is_zero() {
+                let factor = row[column];
+                for (v, p) in row.iter_mut().zip(&pivot_row).skip(column) {
+                    *v -= *p * factor;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    let rank = pivots.len();
+    if rows[rank..].iter().any(|r| !r[unknowns].is_zero()) {
+        return LinearSolution::None;
+    }
+    if rank < unknowns {
+        return LinearSolution::Infinite;
+    }
+
+    debug_assert!(rows[..rank].iter().enumerate().all(|(i, r)| r[i].is_one()));
+    LinearSolution::Unique(rows[..rank].iter().map(|r| r[unknowns]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(n: i128, d: i128) -> Rational {
+        Rational::new(n, d)
+    }
+
+    #[test]
+    fn unique_integral_solution() {
+        // Claw machine of 2024 day 13: 80 A presses and 40 B presses.
+        let solution = solve_linear_system(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+        assert_eq!(solution.integral(), Some(vec![80, 40]));
+        assert!(solution.is_integral());
+
+        // The first column has no pivot in the first row.
+        let solution =
+            solve_linear_system(&[vec![0, 1, 1], vec![2, 0, 1], vec![1, 1, 0]], &[5, 5, 3]);
+        assert_eq!(solution.integral(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn unique_fractional_solution() {
+        let solution = solve_linear_system(&[vec![2, 1], vec![1, -1]], &[1, 1]);
+        assert_eq!(
+            solution,
+            LinearSolution::Unique(vec![ratio(2, 3), ratio(-1, 3)])
+        );
+        assert_eq!(solution.integral(), None);
+
+        let solution = solve_rational_linear_system(&[vec![ratio(1, 2)]], &[ratio(3, 4)]);
+        assert_eq!(solution, LinearSolution::Unique(vec![ratio(3, 2)]));
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let solution = solve_linear_system(&[vec![1, 1], vec![2, 2]], &[1, 3]);
+        assert_eq!(solution, LinearSolution::None);
+        assert!(!solution.is_integral());
+        assert_eq!(
+            solve_linear_system(&[vec![0, 0]], &[1]),
+            LinearSolution::None
+        );
+    }
+
+    #[test]
+    fn underdetermined_system() {
+        let solution = solve_linear_system(&[vec![1, 1], vec![2, 2]], &[1, 2]);
+        assert_eq!(solution, LinearSolution::Infinite);
+        assert_eq!(solution.integral(), None);
+        assert_eq!(
+            solve_linear_system(&[vec![1, 2, 3]], &[6]),
+            LinearSolution::Infinite
+        );
+    }
+
+    #[test]
+    fn more_equations_than_unknowns() {
+        let coefficients = [vec![1, 1], vec![1, -1], vec![3, 1]];
+        assert_eq!(
+            solve_linear_system(&coefficients, &[3, 1, 7]).integral(),
+            Some(vec![2, 1])
+        );
+        assert_eq!(
+            solve_linear_system(&coefficients, &[3, 1, 8]),
+            LinearSolution::None
+        );
+    }
+
+    #[test]
+    fn empty_system() {
+        assert_eq!(
+            solve_linear_system(&[], &[]),
+            LinearSolution::Unique(vec![])
+        );
+        assert_eq!(
+            solve_linear_system(&[vec![]], &[0]),
+            LinearSolution::Unique(vec![])
+        );
+        assert_eq!(solve_linear_system(&[vec![]], &[1]), LinearSolution::None);
+    }
+
+    #[test]
+    #[should_panic(expected = "one constant is needed per equation")]
+    fn missing_constant() {
+        solve_linear_system(&[vec![1, 2], vec![3, 4]], &[1]);
+    }
+}
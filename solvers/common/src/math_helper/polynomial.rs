@@ -0,0 +1,186 @@
+use super::Rational;
+
+/// Rows of successive differences of `values`, until one is made of zeros
+/// only or a single value.
+fn difference_rows(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut rows = vec![values.to_vec()];
+    loop {
+        let last = rows.last().unwrap();
+        if last.len() <= 1 || last.iter().all(|v| *v == 0) {
+            return rows;
+        }
+        let differences = last.windows(2).map(|w| w[1] - w[0]).collect();
+        rows.push(differences);
+    }
+}
+
+/// Next value of a polynomial sequence, using finite differences.
+pub fn extrapolate_forward(values: &[i64]) -> i64 {
+    difference_rows(values)
+        .iter()
+        .filter_map(|r| r.last())
+        .sum()
+}
+
+/// Value preceding a polynomial sequence, using finite differences.
+pub fn extrapolate_backward(values: &[i64]) -> i64 {
+    difference_rows(values)
+        .iter()
+        .rev()
+        .filter_map(|r| r.first())
+        .fold(0, |acc, v| v - acc)
+}
+
+/// Value at `x` of the lowest degree polynomial going through `points`, whose
+/// abscissas must be distinct.
+pub fn lagrange_interpolate(points: &[(i64, i64)], x: i64) -> Rational {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            points.iter().enumerate().filter(|(j, _)| *j != i).fold(
+                Rational::from(yi as i128),
+                |acc, (_, &(xj, _))| {
+                    assert_ne!(xi, xj, "abscissas must be distinct");
+                    acc * Rational::new(x as i128 - xj as i128, xi as i128 - xj as i128)
+                },
+            )
+        })
+        .sum()
+}
+
+/// Smallest and largest integers lying strictly between the two real roots
+/// of `a * x² + b * x + c`, or `None` if there are none. Computed exactly,
+/// without floating point.
+pub fn quadratic_root_bounds(a: i64, b: i64, c: i64) -> Option<(i64, i64)> {
+    assert_ne!(a, 0, "not a quadratic polynomial");
+    // Same roots, with the polynomial negative between them.
+    let (a, b, c) = if a > 0 {
+        (a as i128, b as i128, c as i128)
+    } else {
+        (-(a as i128), -(b as i128), -(c as i128))
+    };
+    let p = |x: i128| (a * x + b) * x + c;
+
+    // The integer with the lowest value is next to the vertex.
+    let vertex = (-b).div_euclid(2 * a);
+    let lowest = if p(vertex) <= p(vertex + 1) {
+        vertex
+    } else {
+        vertex + 1
+    };
+    if p(lowest) >= 0 {
+        return None;
+    }
+
+    let sqrt_delta = (b * b - 4 * a * c).isqrt();
+    let mut low = (-b - sqrt_delta).div_euclid(2 * a).min(lowest);
+    while p(low) >= 0 {
+        low += 1;
+    }
+    while p(low - 1) < 0 {
+        low -= 1;
+    }
+    let mut high = (-b + sqrt_delta).div_euclid(2 * a).max(lowest);
+    while p(high) >= 0 {
+        high -= 1;
+    }
+    while p(high + 1) < 0 {
+        high += 1;
+    }
+
+    Some((low as i64, high as i64))
+}
+
+/// Number of integers lying strictly between the roots of
+/// `a * x² + b * x + c`, see [`quadratic_root_bounds`].
+pub fn count_integers_between_roots(a: i64, b: i64, c: i64) -> u64 {
+    quadratic_root_bounds(a, b, c).map_or(0, |(low, high)| (high - low + 1) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extrapolation() {
+        assert_eq!(extrapolate_forward(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(extrapolate_backward(&[0, 3, 6, 9, 12, 15]), -3);
+        assert_eq!(extrapolate_forward(&[10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(extrapolate_backward(&[10, 13, 16, 21, 30, 45]), 5);
+
+        assert_eq!(extrapolate_forward(&[5, 5, 5]), 5);
+        assert_eq!(extrapolate_backward(&[5, 5, 5]), 5);
+        assert_eq!(extrapolate_forward(&[7]), 7);
+        assert_eq!(extrapolate_backward(&[7]), 7);
+    }
+
+    #[test]
+    fn lagrange() {
+        let square = [(0, 0), (1, 1), (2, 4)];
+        assert_eq!(lagrange_interpolate(&square, 3), Rational::from(9));
+        assert_eq!(lagrange_interpolate(&square, -5), Rational::from(25));
+        assert_eq!(lagrange_interpolate(&[(4, 2)], 10), Rational::from(2));
+
+        // -x² / 2 + 2x
+        let half = [(0, 0), (2, 2), (4, 0)];
+        assert_eq!(lagrange_interpolate(&half, 1), Rational::new(3, 2));
+        assert_eq!(lagrange_interpolate(&half, 2), Rational::from(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "abscissas must be distinct")]
+    fn lagrange_repeated_abscissas() {
+        lagrange_interpolate(&[(1, 2), (3, 4), (1, 3)], 0);
+    }
+
+    #[test]
+    fn root_bounds() {
+        // Race of 2023 day 6: (x - 1.3...)(x - 5.6...)
+        assert_eq!(quadratic_root_bounds(1, -7, 9), Some((2, 5)));
+        assert_eq!(count_integers_between_roots(1, -7, 9), 4);
+        assert_eq!(count_integers_between_roots(1, -30, 200), 9);
+
+        // Roots exactly on integers are excluded.
+        assert_eq!(quadratic_root_bounds(1, -6, 5), Some((2, 4)));
+        assert_eq!(quadratic_root_bounds(1, -5, 6), None);
+        assert_eq!(quadratic_root_bounds(1, -4, 4), None);
+        assert_eq!(quadratic_root_bounds(2, 0, -2), Some((0, 0)));
+
+        // No real roots.
+        assert_eq!(quadratic_root_bounds(1, 0, 1), None);
+        assert_eq!(quadratic_root_bounds(-1, 2, -5), None);
+        assert_eq!(count_integers_between_roots(3, 1, 1), 0);
+
+        // Negative leading coefficient, same roots.
+        assert_eq!(quadratic_root_bounds(-1, 6, -5), Some((2, 4)));
+        assert_eq!(quadratic_root_bounds(-1, 7, -9), Some((2, 5)));
+    }
+
+    #[test]
+    fn root_bounds_of_big_coefficients() {
+        // (x - 10⁹)(x - 3 * 10⁹)
+        assert_eq!(
+            quadratic_root_bounds(1, -4_000_000_000, 3_000_000_000_000_000_000),
+            Some((1_000_000_001, 2_999_999_999))
+        );
+        assert_eq!(
+            quadratic_root_bounds(1, -i64::MAX, 0),
+            Some((1, i64::MAX - 1))
+        );
+        assert_eq!(
+            quadratic_root_bounds(-1, i64::MAX, 0),
+            Some((1, i64::MAX - 1))
+        );
+        assert_eq!(
+            count_integers_between_roots(1, i64::MIN + 1, 0),
+            (i64::MAX - 1) as u64
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not a quadratic polynomial")]
+    fn root_bounds_of_linear_polynomial() {
+        quadratic_root_bounds(0, 1, 1);
+    }
+}
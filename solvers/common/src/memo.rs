@@ -0,0 +1,192 @@
+//! Memoisation scoped to a value instead of a global cache, so that inputs
+//! can be borrowed and runs do not share results.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Cache of results for a recursive function, passed along the recursion.
+///
+/// ```
+/// # use common::memo::Memo;
+/// // Ways to climb `n` steps going up one or two at a time.
+/// fn climb(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     if n < 2 {
+///         return 1;
+///     }
+///     memo.get_or_compute(n, |memo, &n| climb(memo, n - 1) + climb(memo, n - 2))
+/// }
+///
+/// assert_eq!(climb(&mut Memo::new(), 80), 37_889_062_373_143_906);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.cache.get(key).cloned()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// Returns the result cached for `key`, or computes it with `compute`
+    /// which gets the memo back to recurse.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = compute(self, &key);
+        self.insert(key, value.clone());
+        value
+    }
+}
+
+/// Recursive closure owning its [`Memo`], see [`memoize`].
+pub struct Memoized<K, V, F> {
+    memo: Memo<K, V>,
+    f: F,
+}
+
+/// Memoises `f`, which calls its first argument to recurse.
+///
+/// ```
+/// # use common::memo::memoize;
+/// let mut fib = memoize(|fib: &mut dyn FnMut(u64) -> u64, n| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib.call(80), 23_416_728_348_467_685);
+/// ```
+pub fn memoize<K, V, F>(f: F) -> Memoized<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memoized {
+        memo: Memo::new(),
+        f,
+    }
+}
+
+impl<K, V, F> Memoized<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn call(&mut self, key: K) -> V {
+        call_memoized(&mut self.memo, &self.f, key)
+    }
+
+    pub fn memo(&self) -> &Memo<K, V> {
+        &self.memo
+    }
+}
+
+fn call_memoized<K, V, F>(memo: &mut Memo<K, V>, f: &F, key: K) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    memo.get_or_compute(key, |memo, key| {
+        f(&mut |k| call_memoized(memo, f, k), key.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, calls: &mut u64, n: u64) -> u64 {
+        *calls += 1;
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo, &n| {
+            fib(memo, calls, n - 1) + fib(memo, calls, n - 2)
+        })
+    }
+
+    #[test]
+    fn recursion_through_get_or_compute() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        assert_eq!(fib(&mut memo, &mut calls, 80), 23_416_728_348_467_685);
+        assert_eq!(memo.len(), 79);
+        // Every value is computed once, then looked up.
+        assert!(calls < 2 * 80);
+
+        let mut calls = 0;
+        assert_eq!(fib(&mut memo, &mut calls, 80), 23_416_728_348_467_685);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn memoized_closure() {
+        let mut fib = memoize(
+            |fib: &mut dyn FnMut(u64) -> u64, n| {
+                if n < 2 {
+                    n
+                } else {
+                    fib(n - 1) + fib(n - 2)
+                }
+            },
+        );
+        assert_eq!(fib.call(80), 23_416_728_348_467_685);
+        assert_eq!(fib.memo().len(), 81);
+        assert_eq!(fib.memo().get(&10), Some(55));
+    }
+
+    #[test]
+    fn memos_are_isolated() {
+        // The same function and keys over different inputs, as between runs
+        // of a solver.
+        fn count(memo: &mut Memo<usize, usize>, input: &[u8], i: usize) -> usize {
+            if i == input.len() {
+                return 0;
+            }
+            memo.get_or_compute(i, |memo, &i| {
+                count(memo, input, i + 1) + (input[i] == b'#') as usize
+            })
+        }
+
+        let (mut a, mut b) = (Memo::new(), Memo::new());
+        assert_eq!(count(&mut a, b"#.#", 0), 2);
+        assert_eq!(count(&mut b, b"...", 0), 0);
+        assert_eq!(a.get(&0), Some(2));
+        assert_eq!(b.get(&0), Some(0));
+
+        a.clear();
+        assert!(a.is_empty());
+        assert_eq!(count(&mut a, b"##", 0), 2);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::map::Map;
+
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Decodes letters drawn with the AoC block font, either 6 or 10 pixels high.
+pub fn recognize(image: &Map<bool>) -> Result<String, String> {
+    let mut rows: Vec<Vec<bool>> = vec![];
+    for (pos, lit) in image.iter() {
+        if pos.x == 0 {
+            rows.push(vec![]);
+        }
+        rows.last_mut().unwrap().push(*lit);
+    }
+    recognize_rows(rows)
+}
+
+/// Same as [`recognize`], with `#` or `█` as lit pixels.
+pub fn recognize_str(image: &str) -> Result<String, String> {
+    let rows = image
+        .lines()
+        .map(|l| l.chars().map(is_lit).collect())
+        .collect();
+    recognize_rows(rows)
+}
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+fn recognize_rows(mut rows: Vec<Vec<bool>>) -> Result<String, String> {
+    while rows.last().is_some_and(|r| !r.contains(&true)) {
+        rows.pop();
+    }
+    let first_lit_row = rows.iter().position(|r| r.contains(&true)).unwrap_or(0);
+    rows.drain(..first_lit_row);
+
+    let font: &[(char, &str)] = match rows.len() {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        height => return Err(format!("No font for letters {height} pixels high")),
+    };
+
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let column_is_lit = |x: usize| rows.iter().any(|r| r.get(x).copied().unwrap_or(false));
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if !column_is_lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && column_is_lit(x) {
+            x += 1;
+        }
+
+        // Letters usually are separated by a blank column, but wide ones
+        // (like `Y`) may touch their neighbour.
+        let mut cursor = start;
+        while cursor < x {
+            let Some((c, glyph_width)) = font
+                .iter()
+                .map(|(c, g)| (c, g.lines().next().unwrap_or_default().len(), g))
+                .filter(|(_, w, g)| cursor + w <= x && glyph_at(&rows, cursor, *w) == **g)
+                .map(|(c, w, _)| (*c, w))
+                .max_by_key(|(_, w)| *w)
+            else {
+                return Err(format!(
+                    "Unknown letter at column {cursor}:\n{}",
+                    glyph_at(&rows, cursor, x - cursor)
+                ));
+            };
+            text.push(c);
+            cursor += glyph_width;
+        }
+    }
+
+    Ok(text)
+}
+
+fn glyph_at(rows: &[Vec<bool>], x: usize, width: usize) -> String {
+    rows.iter()
+        .map(|r| {
+            (x..x + width)
+                .map(|x| {
+                    if r.get(x).copied().unwrap_or(false) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PartResult;
+
+    fn to_map(glyph: &str) -> Map<bool> {
+        Map::new(
+            glyph
+                .lines()
+                .map(|l| l.chars().map(is_lit).collect())
+                .collect(),
+        )
+    }
+
+    fn glyph(font: &[(char, &'static str)], c: char) -> &'static str {
+        font.iter().find(|(g, _)| *g == c).unwrap().1
+    }
+
+    /// Draws `glyphs` side by side, separated by a blank column.
+    fn draw(glyphs: &[&str]) -> String {
+        let height = glyphs[0].lines().count();
+        (0..height)
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g.lines().nth(y).unwrap())
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn recognizes_every_glyph() {
+        for (c, glyph) in FONT_6.iter().chain(FONT_10.iter()) {
+            assert_eq!(recognize(&to_map(glyph)), Ok(c.to_string()), "{glyph}");
+            let blocks = glyph.replace('#', "█").replace('.', " ");
+            assert_eq!(recognize_str(&blocks), Ok(c.to_string()), "{glyph}");
+        }
+    }
+
+    #[test]
+    fn recognizes_words() {
+        for font in [&FONT_6[..], &FONT_10[..]] {
+            let glyphs = font.iter().map(|(_, g)| *g).collect::<Vec<_>>();
+            let text = font.iter().map(|(c, _)| c).collect::<String>();
+            assert_eq!(recognize_str(&draw(&glyphs)), Ok(text));
+        }
+    }
+
+    #[test]
+    fn splits_touching_letters() {
+        let (y, z) = (glyph(&FONT_6, 'Y'), glyph(&FONT_6, 'Z'));
+        let image = y
+            .lines()
+            .zip(z.lines())
+            .map(|(a, b)| format!("{a}{b}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(recognize_str(&image), Ok("YZ".to_string()));
+    }
+
+    #[test]
+    fn ignores_blank_borders() {
+        let image = format!(
+            "\n{}\n\n",
+            draw(&[glyph(&FONT_6, 'I'), glyph(&FONT_6, 'E')])
+        );
+        assert_eq!(recognize_str(&image), Ok("IE".to_string()));
+    }
+
+    #[test]
+    fn falls_back_to_multi_line() {
+        let image = "#.#.\n.#.#\n#.#.\n.#.#\n#.#.\n.#.#";
+        assert!(recognize_str(image).is_err());
+        assert_eq!(
+            PartResult::ocr(image),
+            PartResult::MultiLine(image.to_string())
+        );
+        assert!(recognize_str("###\n###").is_err());
+    }
+
+    #[test]
+    fn ocr_keeps_the_image() {
+        let image = draw(&[glyph(&FONT_10, 'A'), glyph(&FONT_10, 'B')]);
+        assert_eq!(
+            PartResult::ocr(&image),
+            PartResult::Ocr {
+                text: "AB".to_string(),
+                image
+            }
+        );
+    }
+}
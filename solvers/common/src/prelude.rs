@@ -1,3 +1,4 @@
 pub use crate::inventory::register_solver;
 pub use crate::{DualDaySolver, MonoDaySolver, PartResult};
+pub use common_macros::solver;
 pub use enum_iterator;
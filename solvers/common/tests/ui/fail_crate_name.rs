@@ -0,0 +1,12 @@
+use common::prelude::*;
+
+struct Solver;
+
+#[solver]
+impl MonoDaySolver for Solver {
+    fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+        (PartResult::Unresolved, PartResult::Unresolved)
+    }
+}
+
+fn main() {}
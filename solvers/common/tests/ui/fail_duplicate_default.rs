@@ -0,0 +1,21 @@
+use common::prelude::*;
+
+struct Solver;
+
+#[solver(year = 2024, day = 1)]
+impl MonoDaySolver for Solver {
+    fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+        (PartResult::Unresolved, PartResult::Unresolved)
+    }
+}
+
+struct OtherSolver;
+
+#[solver(year = 2024, day = 1)]
+impl MonoDaySolver for OtherSolver {
+    fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+        (PartResult::Unresolved, PartResult::Unresolved)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use common::prelude::*;
+
+#[derive(Debug)]
+struct Solver;
+
+#[solver(year = 2024, day = 1)]
+impl Clone for Solver {
+    fn clone(&self) -> Self {
+        Solver
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use common::prelude::*;
+
+struct Solver;
+
+#[solver(year = 2024, day = 1, label = "all")]
+impl MonoDaySolver for Solver {
+    fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+        (PartResult::Unresolved, PartResult::Unresolved)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+// Duplicates in distinct modules compile, they are only reported at runtime.
+use common::inventory::Solvers;
+
+mod a {
+    use common::prelude::*;
+
+    struct Solver;
+
+    #[solver(year = 2024, day = 1)]
+    impl MonoDaySolver for Solver {
+        fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+            (PartResult::Unresolved, PartResult::Unresolved)
+        }
+    }
+}
+
+mod b {
+    use common::prelude::*;
+
+    struct Solver;
+
+    #[solver(year = 2024, day = 1)]
+    impl MonoDaySolver for Solver {
+        fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+            (PartResult::Unresolved, PartResult::Unresolved)
+        }
+    }
+}
+
+fn main() {
+    assert!(Solvers::new().is_err());
+}
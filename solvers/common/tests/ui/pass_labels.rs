@@ -0,0 +1,36 @@
+use common::{inventory::Solvers, prelude::*};
+
+struct Solver;
+
+#[solver(year = 2024, day = 1)]
+impl MonoDaySolver for Solver {
+    fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+        (PartResult::Unresolved, PartResult::Unresolved)
+    }
+}
+
+struct SolverLabeled;
+
+#[solver(year = 2024, day = 1, label = "labeled", tags = [Math])]
+impl DualDaySolver for SolverLabeled {
+    fn solve_1(&self, _input: &str) -> PartResult {
+        PartResult::Unresolved
+    }
+
+    fn solve_2(&self, _input: &str) -> PartResult {
+        PartResult::Unresolved
+    }
+}
+
+struct SolverOtherDay;
+
+#[solver(year = 2024, day = 2, label = "labeled")]
+impl MonoDaySolver for SolverOtherDay {
+    fn solve(&self, _input: &str) -> (PartResult, PartResult) {
+        (PartResult::Unresolved, PartResult::Unresolved)
+    }
+}
+
+fn main() {
+    assert!(Solvers::new().is_ok());
+}
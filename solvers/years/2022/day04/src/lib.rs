@@ -1,46 +1,32 @@
-use std::ops::RangeInclusive;
+use common::{intervals::IntervalSet, prelude::*};
 
 register_solver!(2022, 4, Solver);
-use common::prelude::*;
-
 pub struct Solver;
 
 impl DualDaySolver for Solver {
     fn solve_1(&self, input: &str) -> PartResult {
         let res = get_ranges(input)
-            .filter(|(a, b)| {
-                (a.contains(b.start()) && a.contains(b.end()))
-                    || (b.contains(a.start()) && b.contains(a.end()))
-            })
+            .filter(|(a, b)| a.is_subset(b) || b.is_subset(a))
             .count();
 
         PartResult::new(res)
     }
 
     fn solve_2(&self, input: &str) -> PartResult {
-        let res = get_ranges(input)
-            .filter(|(a, b)| {
-                a.contains(b.start())
-                    || a.contains(b.end())
-                    || b.contains(a.start())
-                    || b.contains(a.end())
-            })
-            .count();
+        let res = get_ranges(input).filter(|(a, b)| !a.is_disjoint(b)).count();
 
         PartResult::new(res)
     }
 }
 
-fn get_ranges(
-    input: &str,
-) -> impl Iterator<Item = (RangeInclusive<u64>, RangeInclusive<u64>)> + '_ {
+fn get_ranges(input: &str) -> impl Iterator<Item = (IntervalSet, IntervalSet)> + '_ {
     input.split('\n').map(|l| {
         let mut it = l.split(',');
         (to_range(it.next().unwrap()), to_range(it.next().unwrap()))
     })
 }
 
-fn to_range(s: &str) -> RangeInclusive<u64> {
+fn to_range(s: &str) -> IntervalSet {
     let mut it = s.split('-');
-    it.next().unwrap().parse().unwrap()..=it.next().unwrap().parse().unwrap()
+    IntervalSet::from(it.next().unwrap().parse().unwrap()..=it.next().unwrap().parse().unwrap())
 }
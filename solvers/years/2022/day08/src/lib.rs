@@ -1,28 +1,41 @@
-use common::prelude::*;
+use common::{
+    map::{vec2, Digit, Map, Vec2},
+    prelude::*,
+};
 use rayon::prelude::*;
 use take_until::TakeUntilExt;
 
 register_solver!(2022, 8, Solver);
 pub struct Solver;
 
-type Map = Vec<Vec<u32>>;
-
 impl MonoDaySolver for Solver {
     fn solve(&self, input: &str) -> (PartResult, PartResult) {
-        let map: Map = input
-            .split('\n')
-            .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-            .collect();
+        let map = Map::<Digit>::parse(input).unwrap();
 
-        let visible_trees = tree_iter(&map)
-            .filter(|(current, (x, y))| {
-                line_col_iter(&map, (*x, *y)).any(|mut line| line.all(|h| h < *current))
-            })
-            .count();
+        // Looking at each rotation of the forest from the left, a tree is
+        // visible when it is taller than all the previous ones of its row.
+        let mut visible = Map::filled(map.width(), map.height(), false);
+        for view in [
+            map.view(),
+            map.rotated_right(),
+            map.rotated_180(),
+            map.rotated_left(),
+        ] {
+            for (y, row) in view.rows().enumerate() {
+                let mut tallest = None;
+                for (x, height) in row.enumerate() {
+                    if tallest.is_none_or(|t| height > t) {
+                        visible[view.map_pos(vec2(x as i64, y as i64))] = true;
+                        tallest = Some(height);
+                    }
+                }
+            }
+        }
+        let visible_trees = visible.iter().filter(|(_, v)| **v).count();
 
         let max_scenic_score = tree_iter(&map)
-            .map(|(current, (x, y))| {
-                line_col_iter(&map, (x, y))
+            .map(|(current, pos)| {
+                line_col_iter(&map, pos)
                     .map(|line| line.take_until(|h| *h >= current).count())
                     .product::<usize>()
             })
@@ -36,21 +49,18 @@ impl MonoDaySolver for Solver {
     }
 }
 
-fn tree_iter(map: &Map) -> impl ParallelIterator<Item = (u32, (usize, usize))> + '_ {
-    map.par_iter()
+fn tree_iter(map: &Map<Digit>) -> impl ParallelIterator<Item = (Digit, Vec2)> + '_ {
+    map.as_slice()
+        .par_iter()
         .enumerate()
-        .flat_map(|(y, l)| l.par_iter().enumerate().map(move |(x, v)| (*v, (x, y))))
+        .map(|(i, v)| (*v, map.pos_of(i)))
 }
 
-fn line_col_iter<'a>(
-    map: &'a Map,
-    (x, y): (usize, usize),
-) -> impl Iterator<Item = impl Iterator<Item = u32> + 'a> {
-    let up = Box::new((0..y).rev().map(move |i| map[i][x]));
-    let down = Box::new((y + 1..map.len()).map(move |i| map[i][x]));
-    let left = Box::new((0..x).rev().map(move |i| map[y][i]));
-    let right = Box::new((x + 1..map[0].len()).map(move |i| map[y][i]));
-
-    let a: [Box<dyn Iterator<Item = u32> + Send + 'a>; 4] = [up, down, left, right];
-    a.into_iter()
+fn line_col_iter(
+    map: &Map<Digit>,
+    pos: Vec2,
+) -> impl Iterator<Item = impl Iterator<Item = Digit> + '_> + '_ {
+    [Vec2::NORTH, Vec2::SOUTH, Vec2::WEST, Vec2::EAST]
+        .into_iter()
+        .map(move |d| map.iter_from_point(pos, d).skip(1).map(|(_, h)| *h))
 }
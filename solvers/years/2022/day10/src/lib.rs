@@ -50,6 +50,6 @@ impl MonoDaySolver for Solver {
             };
         }
 
-        (PartResult::new(sum), PartResult::new(screen.trim()))
+        (PartResult::new(sum), PartResult::ocr(screen))
     }
 }
@@ -93,7 +93,7 @@ fn parse(input: &str) -> Packet {
         let mut cur = 0;
         let mut split = vec![];
         let input = &input[1..input.len() - 1];
-        for (i, c) in input.chars().enumerate() {
+        for (i, c) in input.char_indices() {
             match c {
                 '[' => nest += 1,
                 ']' => nest -= 1,
@@ -1,17 +1,18 @@
-use std::collections::HashSet;
-
-use common::prelude::*;
+use common::{
+    map::{vec2, SparseMap, Vec2},
+    prelude::*,
+};
 
 register_solver!(2022, 14, Solver);
 pub struct Solver;
 
-const START: (i32, i32) = (500, 0);
+const START: Vec2 = Vec2 { x: 500, y: 0 };
 
 impl DualDaySolver for Solver {
     fn solve_1(&self, input: &str) -> PartResult {
         let mut map = parse(input);
 
-        let max_y = map.iter().map(|(_x, y)| y).max().unwrap() + 1;
+        let max_y = map.bounding_box().unwrap().1.y + 1;
         let mut sand_units = 0;
 
         'l: loop {
@@ -19,11 +20,11 @@ impl DualDaySolver for Solver {
 
             while let Some(next_pos) = sand_next_pos(&map, cur_pos, None) {
                 cur_pos = next_pos;
-                if cur_pos.1 == max_y {
+                if cur_pos.y == max_y {
                     break 'l;
                 }
             }
-            map.insert(cur_pos);
+            map.insert(cur_pos, 'o');
             sand_units += 1;
         }
 
@@ -33,7 +34,7 @@ impl DualDaySolver for Solver {
     fn solve_2(&self, input: &str) -> PartResult {
         let mut map = parse(input);
 
-        let floor = map.iter().map(|(_x, y)| y).max().unwrap() + 2;
+        let floor = map.bounding_box().unwrap().1.y + 2;
 
         let mut sand_units = 0;
 
@@ -43,7 +44,7 @@ impl DualDaySolver for Solver {
             while let Some(next_pos) = sand_next_pos(&map, cur_pos, Some(floor)) {
                 cur_pos = next_pos;
             }
-            map.insert(cur_pos);
+            map.insert(cur_pos, 'o');
             sand_units += 1;
             if cur_pos == START {
                 break;
@@ -54,29 +55,24 @@ impl DualDaySolver for Solver {
     }
 }
 
-fn parse(input: &str) -> HashSet<(i32, i32)> {
-    let mut map = HashSet::new();
+fn parse(input: &str) -> SparseMap<char> {
+    let mut map = SparseMap::new();
     for lines in input.split('\n').map(|l| {
         l.split(" -> ")
             .map(|coords| {
                 let mut it = coords.split(',');
-                (
-                    it.next().unwrap().parse::<i32>().unwrap(),
-                    it.next().unwrap().parse::<i32>().unwrap(),
+                vec2(
+                    it.next().unwrap().parse::<i64>().unwrap(),
+                    it.next().unwrap().parse::<i64>().unwrap(),
                 )
             })
             .collect::<Vec<_>>()
     }) {
         for l in lines.windows(2) {
-            let (xa, ya) = l[0];
-            let (xb, yb) = l[1];
-            if xa == xb {
-                for y in num::range_inclusive(ya, yb) {
-                    map.insert((xa, y));
-                }
-            } else {
-                for x in num::range_inclusive(xa, xb) {
-                    map.insert((x, ya));
+            let (a, b) = (l[0], l[1]);
+            for x in a.x.min(b.x)..=a.x.max(b.x) {
+                for y in a.y.min(b.y)..=a.y.max(b.y) {
+                    map.insert(vec2(x, y), '#');
                 }
             }
         }
@@ -84,16 +80,15 @@ fn parse(input: &str) -> HashSet<(i32, i32)> {
     map
 }
 
-fn sand_next_pos(
-    map: &HashSet<(i32, i32)>,
-    (x, y): (i32, i32),
-    floor: Option<i32>,
-) -> Option<(i32, i32)> {
+fn sand_next_pos(map: &SparseMap<char>, pos: Vec2, floor: Option<i64>) -> Option<Vec2> {
     if let Some(floor) = floor {
-        if y == floor - 1 {
+        if pos.y == floor - 1 {
             return None;
         }
     }
-    let tests = [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)];
-    tests.into_iter().find(|t| map.get(t).is_none())
+    let tests = [Vec2::SOUTH, Vec2::SOUTH_WEST, Vec2::SOUTH_EAST];
+    tests
+        .into_iter()
+        .map(|d| pos + d)
+        .find(|t| !map.contains(*t))
 }
@@ -107,7 +107,7 @@ fn solve<NumberMetadata: Default>(
 
     let mut numbers = vec![];
 
-    for (y, line) in map.inner().iter().enumerate() {
+    for (y, line) in map.rows().enumerate() {
         let mut current_number: Option<Number<NumberMetadata>> = None;
 
         // Iterating in reverse allows easy parsing of the numbers (by reading
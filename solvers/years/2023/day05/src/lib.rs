@@ -1,69 +1,23 @@
-use std::ops::Range;
-
 use chumsky::prelude::*;
-use common::prelude::*;
-use range_collections::{range_set::RangeSetRange, RangeSet2};
+use common::{
+    intervals::{IntervalSet, PiecewiseShift},
+    prelude::*,
+};
 
 register_solver!(2023, 5, Solver);
 pub struct Solver;
 
-#[derive(Debug, Clone, Copy)]
-struct RangeMapping {
-    destination: i64,
-    source: i64,
-    size: i64,
-}
-
-impl RangeMapping {
-    fn map(&self, element: i64) -> Option<i64> {
-        if self.source_range().contains(&element) {
-            Some(self.destination + (element - self.source))
-        } else {
-            None
-        }
-    }
-
-    fn source_range(&self) -> Range<i64> {
-        self.source..self.source + self.size
-    }
-}
-
 #[derive(Debug)]
 struct Mapping {
     _from: String,
     _to: String,
-    mappings: Vec<RangeMapping>,
-}
-type RangeSet = RangeSet2<i64>;
-
-impl Mapping {
-    fn map(&self, element: i64) -> i64 {
-        self.mappings
-            .iter()
-            .find_map(|r| r.map(element))
-            .unwrap_or(element)
-    }
-
-    fn map_range(&self, mut range: RangeSet) -> RangeSet {
-        let mut res = RangeSet::empty();
-        for mapping in &self.mappings {
-            // Extract from input range values that are mapped by the current
-            // mapping and add it to ouput
-            let to_map = RangeSet::from(mapping.source_range()) & &range;
-            res |= offset_range(&to_map, mapping.destination - mapping.source);
-
-            // Remove processed range from input
-            range ^= to_map;
-        }
-        // Map unmapped ranges to identity
-        res | range
-    }
+    shift: PiecewiseShift,
 }
 
 #[derive(Debug)]
 struct Input {
     individual_seeds: Vec<i64>,
-    range_seeds: RangeSet,
+    range_seeds: IntervalSet,
     mappings: Vec<Mapping>,
 }
 
@@ -75,7 +29,7 @@ impl DualDaySolver for Solver {
 
         for mapping in input.mappings {
             for element in &mut elements {
-                *element = mapping.map(*element);
+                *element = mapping.shift.apply(*element);
             }
         }
 
@@ -88,13 +42,10 @@ impl DualDaySolver for Solver {
         let mut ranges = input.range_seeds.clone();
 
         for mapping in &input.mappings {
-            ranges = mapping.map_range(ranges);
+            ranges = mapping.shift.apply_set(&ranges);
         }
 
-        PartResult::new(match ranges.iter().next().unwrap() {
-            RangeSetRange::Range(r) => r.start,
-            RangeSetRange::RangeFrom(_) => unreachable!(),
-        })
+        PartResult::new(ranges.min().unwrap())
     }
 }
 
@@ -108,21 +59,16 @@ fn parser() -> impl Parser<char, Input, Error = Simple<char>> {
                 .exactly(2)
                 .map(|n| n[0]..n[0] + n[1]))
             .separated_by(text::whitespace()))
-            .map(|ranges| {
-                ranges
-                    .into_iter()
-                    .fold(RangeSet::empty(), |acc, r| acc | RangeSet::from(r))
-            }),
+            .map(IntervalSet::from_iter),
         ),
     );
 
     let range = number
         .separated_by(text::whitespace())
         .exactly(3)
-        .map(|numbers| RangeMapping {
-            destination: numbers[0],
-            source: numbers[1],
-            size: numbers[2],
+        .map(|numbers| {
+            let (destination, source, size) = (numbers[0], numbers[1], numbers[2]);
+            (source..source + size, destination - source)
         });
 
     let mapping = text::ident()
@@ -133,7 +79,7 @@ fn parser() -> impl Parser<char, Input, Error = Simple<char>> {
         .map(|((from, to), ranges)| Mapping {
             _from: from,
             _to: to,
-            mappings: ranges,
+            shift: ranges.into_iter().collect(),
         });
 
     seeds
@@ -146,15 +92,3 @@ fn parser() -> impl Parser<char, Input, Error = Simple<char>> {
         })
         .then_ignore(end())
 }
-
-// Offset a range by the provided amount.
-fn offset_range(range: &RangeSet, offset: i64) -> RangeSet {
-    let mut res = RangeSet::empty();
-    for part in range.iter() {
-        res |= match part {
-            RangeSetRange::Range(s) => RangeSet::from(*s.start + offset..*s.end + offset),
-            RangeSetRange::RangeFrom(_) => unreachable!(),
-        };
-    }
-    res
-}
@@ -1,47 +1,24 @@
 use chumsky::prelude::*;
 
-use common::prelude::*;
+use common::{math_helper::count_integers_between_roots, prelude::*};
 
 register_solver!(2023, 6, Solver);
 pub struct Solver;
 
 #[derive(Debug)]
 struct Run {
-    time: f64,
-    distance: f64,
+    time: i64,
+    distance: i64,
 }
 
 impl Run {
     fn number_of_way_to_win(&self) -> u64 {
-        // To find at which time we beat the current record, we're going to
-        // solve the equation:
-        //      `distance = v_start * t_moving`
-        // <=>  `distance = t_pressed * (t_run - t_pressed)`
-        // <=>  `-t_pressed² + t_pressed * t_run - distance = 0`
-        // Which forms a quadratic function in which t_pressed is the unknown.
-
-        // Solve the roots of this function to know in what t_pressed time our
-        // boat would win.
-        let a = -1.0;
-        let b = self.time;
-        let c = -self.distance;
-        let delta = (b * b) - 4.0 * a * c;
-        assert!(
-            delta >= 0.0,
-            "Assume we have a lower and an upper bound to the result"
-        );
-        let root1 = (-b + delta.sqrt()) / (2.0 * a);
-        let root2 = (-b - delta.sqrt()) / (2.0 * a);
-
-        // Find first and last time for which we would have won.
-        //
-        // Use "+ 1.0 and floor()" instead of ceil() to exclude an exact integer
-        // (eg. if the exact record would be reach by a 20ms long press, we need to press for at least 21ms).
-        let from = (root1 + 1.0).floor() as u64;
-        // Same as above but reverse for end of range.
-        let to = (root2 - 1.0).ceil() as u64;
-
-        to - from + 1
+        // We beat the record when:
+        //      `t_pressed * (t_run - t_pressed) > distance`
+        // <=>  `t_pressed² - t_run * t_pressed + distance < 0`
+        // Which holds for t_pressed strictly between the roots of this
+        // quadratic function.
+        count_integers_between_roots(1, -self.time, self.distance)
     }
 }
 
@@ -60,7 +37,7 @@ impl DualDaySolver for Solver {
 }
 
 fn parser_1() -> impl Parser<char, Vec<Run>, Error = Simple<char>> {
-    let number = text::int(10).map(|i: String| i.parse::<f64>().unwrap());
+    let number = text::int(10).map(|i: String| i.parse::<i64>().unwrap());
 
     just("Time:")
         .padded()
@@ -83,7 +60,7 @@ fn parser_2() -> impl Parser<char, Run, Error = Simple<char>> {
     // Parse space separated number as one big number.
     let number = text::int(10)
         .separated_by(text::whitespace())
-        .map(|s: Vec<String>| s.into_iter().collect::<String>().parse::<f64>().unwrap());
+        .map(|s: Vec<String>| s.into_iter().collect::<String>().parse::<i64>().unwrap());
 
     just("Time:")
         .padded()
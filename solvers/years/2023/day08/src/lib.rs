@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use chumsky::prelude::*;
-use common::prelude::*;
+use common::{math_helper, prelude::*};
 
 use itertools::Itertools;
 
@@ -53,7 +53,7 @@ fn solve(
     let mut counts = vec![];
 
     for start in starts {
-        let mut step_count = 0_u64;
+        let mut step_count = 0_i64;
         let mut current = start;
 
         while !end_condition(current) {
@@ -70,7 +70,7 @@ fn solve(
         counts.push(step_count);
     }
 
-    PartResult::new(counts.into_iter().reduce(num::integer::lcm).unwrap())
+    PartResult::new(counts.into_iter().reduce(math_helper::lcm).unwrap())
 }
 
 fn parser() -> impl Parser<char, Input, Error = Simple<char>> {
@@ -3,8 +3,6 @@ use common::{
     prelude::*,
 };
 
-use itertools::Itertools;
-
 register_solver!(2024, 4, Solver);
 pub struct Solver;
 
@@ -60,5 +58,5 @@ impl DualDaySolver for Solver {
 }
 
 fn parse(input: &str) -> Map<char> {
-    Map::new(input.lines().map(|l| l.chars().collect_vec()).collect_vec())
+    Map::parse(input).unwrap()
 }
@@ -1,26 +1,43 @@
 use std::{
     collections::HashSet,
-    f32::consts::PI,
     sync::{Arc, Mutex},
 };
 
 use common::{
-    map::{Map, Vec2},
+    cycle, debug,
+    map::{
+        render::{Color, Renderer},
+        Direction, FromGridChar, Map, Vec2,
+    },
     prelude::*,
 };
 
-use chumsky::prelude::*;
-
 register_solver!(2024, 6, Solver);
 pub struct Solver;
 
 impl DualDaySolver for Solver {
     fn solve_1(&self, input: &str) -> PartResult {
-        let mut map = parser().parse(input).unwrap();
+        let mut map = Map::<Element>::parse(input).unwrap();
         let (start_pos, start_dir) = get_starting_pos_dir(&map);
 
         simulate(&mut map, start_pos, start_dir, |_| {});
 
+        if debug::enabled() {
+            Renderer::with_cells(&map, |e| match e {
+                Element::Nothing => ".".to_string(),
+                Element::Obstruction => "#".to_string(),
+                Element::Guard(dirs) if dirs.len() == 1 => {
+                    let d = *dirs.iter().next().unwrap();
+                    Direction::try_from(d)
+                        .map_or('X', |d| d.to_arrow())
+                        .to_string()
+                }
+                Element::Guard(_) => "+".to_string(),
+            })
+            .color_by(|e| matches!(e, Element::Guard(_)).then_some(Color::Yellow))
+            .debug_print();
+        }
+
         let res = map
             .iter()
             .filter(|(_, element)| matches!(element, Element::Guard(_)))
@@ -30,7 +47,7 @@ impl DualDaySolver for Solver {
     }
 
     fn solve_2(&self, input: &str) -> PartResult {
-        let mut map = parser().parse(input).unwrap();
+        let mut map = Map::<Element>::parse(input).unwrap();
         let (start_pos, start_dir) = get_starting_pos_dir(&map);
 
         let mut default_map = map.clone();
@@ -60,20 +77,21 @@ impl DualDaySolver for Solver {
 
                 let possible_obstructions = Arc::clone(&possible_obstructions);
                 let default_map = Arc::clone(&starting_map);
+                let start = (p.current_pos, p.current_dir);
                 scope.spawn(move |_| {
-                    let mut obstruction_sim_map = (*default_map).clone();
-                    *obstruction_sim_map.get_mut(obstruction_block).unwrap() = Element::Obstruction;
-                    *obstruction_sim_map.get_mut(p.current_pos).unwrap() =
-                        Element::Guard(HashSet::from([p.current_dir]));
-
-                    if simulate(
-                        &mut obstruction_sim_map,
-                        p.current_pos,
-                        p.current_dir,
-                        |_| {},
-                    )
-                    .is_cyclic
-                    {
+                    let guard_loops = cycle::try_find_cycle(start, |&(pos, dir)| {
+                        let next_pos = pos + dir;
+                        if next_pos == obstruction_block {
+                            return Some((pos, dir.rotate_right()));
+                        }
+                        match default_map.get(next_pos)? {
+                            Element::Obstruction => Some((pos, dir.rotate_right())),
+                            Element::Nothing | Element::Guard(_) => Some((next_pos, dir)),
+                        }
+                    })
+                    .is_some();
+
+                    if guard_loops {
                         possible_obstructions
                             .lock()
                             .unwrap()
@@ -99,10 +117,6 @@ fn get_starting_pos_dir(map: &Map<Element>) -> (Vec2, Vec2) {
     (start_pos, start_dir)
 }
 
-struct SimulationResult {
-    is_cyclic: bool,
-}
-
 struct StepHookParam<'a> {
     map: &'a Map<Element>,
     current_pos: Vec2,
@@ -114,11 +128,11 @@ fn simulate(
     start_pos: Vec2,
     start_dir: Vec2,
     mut step_hook: impl FnMut(StepHookParam),
-) -> SimulationResult {
+) {
     let mut current_pos = start_pos;
     let mut current_dir = start_dir;
 
-    let is_cyclic = loop {
+    loop {
         step_hook(StepHookParam {
             current_dir,
             current_pos,
@@ -129,8 +143,8 @@ fn simulate(
                 let next_pos = current_pos + current_dir;
                 current_pos = next_pos
             }
-            Some(Element::Obstruction) => current_dir = current_dir.rotate(PI / 2.0),
-            None => break false,
+            Some(Element::Obstruction) => current_dir = current_dir.rotate_right(),
+            None => break,
         }
         match map.get_mut(current_pos).unwrap() {
             e @ Element::Nothing | e @ Element::Obstruction => {
@@ -138,13 +152,11 @@ fn simulate(
             }
             Element::Guard(v) => {
                 if !v.insert(current_dir) {
-                    break true;
+                    break;
                 }
             }
         };
-    };
-
-    SimulationResult { is_cyclic }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -154,16 +166,13 @@ enum Element {
     Guard(HashSet<Vec2>),
 }
 
-fn parser() -> impl Parser<char, Map<Element>, Error = Simple<char>> {
-    let nothing = just(".").to(Element::Nothing);
-    let obstruction = just("#").to(Element::Obstruction);
-    let guard = just("^").to(Element::Guard(HashSet::from([Vec2::NORTH])));
-
-    let element = nothing.or(obstruction).or(guard);
-
-    let line = element.repeated();
-
-    line.separated_by(text::newline())
-        .map(Map::new)
-        .then_ignore(end())
+impl FromGridChar for Element {
+    fn from_grid_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Element::Nothing),
+            '#' => Some(Element::Obstruction),
+            '^' => Some(Element::Guard(HashSet::from([Vec2::NORTH]))),
+            _ => None,
+        }
+    }
 }
@@ -1,10 +1,17 @@
-use common::{math_helper::num_digits_in_base_10, prelude::*};
+use common::{math_helper::Digits, prelude::*};
 
 use chumsky::prelude::*;
 use itertools::{repeat_n, Itertools};
 use rayon::prelude::*;
 
-register_solver!(2024, 7, Solver);
+register_solver!(
+    2024,
+    7,
+    Solver,
+    title = "Bridge Repair",
+    description = "Recursive search undoing operators from the result",
+    tags = [Math],
+);
 pub struct Solver;
 
 impl DualDaySolver for Solver {
@@ -33,7 +40,15 @@ impl DualDaySolver for Solver {
     }
 }
 
-register_solver!(2024, 7, SolverIterativeBruteForce, "iterative");
+register_solver!(
+    2024,
+    7,
+    SolverIterativeBruteForce,
+    "iterative",
+    title = "Bridge Repair",
+    description = "Brute force over all operator combinations",
+    tags = [Math],
+);
 pub struct SolverIterativeBruteForce;
 
 impl DualDaySolver for SolverIterativeBruteForce {
@@ -103,7 +118,7 @@ impl Operator {
         match self {
             Operator::Add => a + b,
             Operator::Multiply => a * b,
-            Operator::Concat => a * 10_i64.pow(num_digits_in_base_10(b)) as i64 + b,
+            Operator::Concat => a.concat(b),
         }
     }
 
@@ -112,12 +127,9 @@ impl Operator {
             Operator::Add => (from > number).then(|| from - number),
             Operator::Multiply => (from % number == 0).then(|| from / number),
             Operator::Concat => {
-                let ten_power = 10_i64.pow(num_digits_in_base_10(number));
-                if (from - number) % ten_power == 0 {
-                    Some(from / ten_power)
-                } else {
-                    None
-                }
+                let index = from.num_digits().saturating_sub(number.num_digits());
+                let (head, tail) = from.split_at_digit(index);
+                (tail == number).then_some(head)
             }
         }
     }
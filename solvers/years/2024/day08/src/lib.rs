@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use common::{map::Map, prelude::*};
+use common::{
+    map::{FromGridChar, Map},
+    prelude::*,
+};
 
-use chumsky::prelude::*;
 use itertools::Itertools;
 
 register_solver!(2024, 8, Solver);
@@ -54,7 +56,7 @@ impl DualDaySolver for Solver {
 }
 
 fn parse(input: &str) -> (Map<Cell>, HashMap<char, Vec<common::map::Vec2>>) {
-    let map = parser().parse(input).unwrap();
+    let map = Map::<Cell>::parse(input).unwrap();
 
     let antennas = map
         .iter()
@@ -72,12 +74,11 @@ enum Cell {
     Antenna(char),
 }
 
-fn parser() -> impl Parser<char, Map<Cell>, Error = Simple<char>> {
-    let cell = just(".")
-        .to(Cell::Empty)
-        .or(text::newline().not().map(Cell::Antenna));
-
-    let line = cell.repeated();
-
-    line.separated_by(text::newline()).map(Map::new)
+impl FromGridChar for Cell {
+    fn from_grid_char(c: char) -> Option<Self> {
+        Some(match c {
+            '.' => Cell::Empty,
+            c => Cell::Antenna(c),
+        })
+    }
 }
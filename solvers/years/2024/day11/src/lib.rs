@@ -1,4 +1,4 @@
-use common::{math_helper::num_digits_in_base_10, prelude::*};
+use common::{math_helper::Digits, memo::Memo, prelude::*};
 
 register_solver!(2024, 11, Solver);
 pub struct Solver;
@@ -14,30 +14,29 @@ impl DualDaySolver for Solver {
 }
 
 fn solve(input: &str, iteration_count: u32) -> u64 {
+    let mut memo = Memo::new();
     input
         .split_whitespace()
         .map(|n| n.parse().unwrap())
-        .map(|n| compute(iteration_count, n))
+        .map(|n| compute(&mut memo, iteration_count, n))
         .sum::<u64>()
 }
 
-#[cached::proc_macro::cached]
-fn compute(iteration: u32, value: u64) -> u64 {
+fn compute(memo: &mut Memo<(u32, u64), u64>, iteration: u32, value: u64) -> u64 {
     if iteration == 0 {
         return 1;
     }
+    memo.get_or_compute((iteration, value), |memo, _| {
+        let next = iteration - 1;
 
-    let next = iteration - 1;
-
-    let num_digits = num_digits_in_base_10(value as i64);
-    if value == 0 {
-        compute(next, 1)
-    } else if num_digits % 2 == 0 {
-        let ten_power = 10_u64.pow(num_digits / 2);
-        let left_part = value / ten_power;
-        let right_part = value - (left_part * ten_power);
-        compute(next, left_part) + compute(next, right_part)
-    } else {
-        compute(next, value * 2024)
-    }
+        let num_digits = value.num_digits();
+        if value == 0 {
+            compute(memo, next, 1)
+        } else if num_digits.is_multiple_of(2) {
+            let (left_part, right_part) = value.split_at_digit(num_digits / 2);
+            compute(memo, next, left_part) + compute(memo, next, right_part)
+        } else {
+            compute(memo, next, value * 2024)
+        }
+    })
 }
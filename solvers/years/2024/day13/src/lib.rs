@@ -1,5 +1,6 @@
 use common::{
     map::{vec2, Vec2},
+    math_helper::{solve_linear_system, LinearSolution},
     prelude::*,
 };
 
@@ -34,28 +35,18 @@ impl DualDaySolver for Solver {
 }
 
 fn compute_cost(Machine { a, b, prize }: Machine) -> Option<i64> {
-    // Solving the linear system
-    let a_num = b.x * prize.y - b.y * prize.x;
-    let a_denom = a.y * b.x - a.x * b.y;
-
-    let b_num = a.x * prize.y - a.y * prize.x;
-    let b_denom = b.y * a.x - b.x * a.y;
+    let solution = solve_linear_system(&[vec![a.x, b.x], vec![a.y, b.y]], &[prize.x, prize.y]);
 
     // There is always exactly one solution in the input, the "finding the
     // minimum bit" is just to make us do harder math than needed :D
-    assert!(a_denom != 0 && b_denom != 0, "More than one solution found");
-
-    let a_remainder = a_num % a_denom;
-    let b_remainder = b_num % b_denom;
-
-    if a_remainder != 0 || b_remainder != 0 {
-        return None;
-    }
+    assert!(
+        solution != LinearSolution::Infinite,
+        "More than one solution found"
+    );
 
-    let a = a_num / a_denom;
-    let b = b_num / b_denom;
+    let presses = solution.integral()?;
 
-    Some(3 * a + b)
+    Some((3 * presses[0] + presses[1]) as i64)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1,7 +1,8 @@
 use std::ops::Neg;
 
 use common::{
-    map::{vec2, Vec2},
+    cycle,
+    map::{vec2, Map, Vec2},
     prelude::*,
 };
 
@@ -47,10 +48,14 @@ impl DualDaySolver for Solver {
     fn solve_2(&self, input: &str) -> PartResult {
         let mut input = parser().parse(input).unwrap();
 
-        // Map size is made of prime number, robots move in a modulus way so
-        // they will come back to their original position at the lowest common
-        // multiple, which for prime numbers is a * b.
-        let max_iterations = input.map_size.x * input.map_size.y;
+        // Robots move in a modulus way so they eventually come back to their
+        // original positions, there is no need to look further.
+        let max_iterations = cycle::brent(input.clone(), |input| {
+            let mut input = input.clone();
+            input.step();
+            input
+        })
+        .length;
 
         // Due to the nature of today's problem, we have to use an heuristic to
         // estimate when we encounter the christmas tree.
@@ -78,33 +83,27 @@ impl DualDaySolver for Solver {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Input {
     map_size: Vec2,
+    floor: Map<()>,
     robots: Vec<Robot>,
 }
 impl Input {
     fn step(&mut self) {
+        let floor = self.floor.wrapping();
         for r in &mut self.robots {
-            r.step(self.map_size);
+            r.pos = floor.step(r.pos, r.velocity);
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Robot {
     pos: Vec2,
     velocity: Vec2,
 }
 
-impl Robot {
-    fn step(&mut self, map_size: Vec2) {
-        self.pos = self.pos + self.velocity;
-        self.pos.x = self.pos.x.rem_euclid(map_size.x);
-        self.pos.y = self.pos.y.rem_euclid(map_size.y);
-    }
-}
-
 fn parser() -> impl Parser<char, Input, Error = Simple<char>> {
     let number =
         just("-")
@@ -135,8 +134,12 @@ fn parser() -> impl Parser<char, Input, Error = Simple<char>> {
     (vec.clone().then_ignore(text::newline()))
         .or_not()
         .then(robots)
-        .map(|(map_size, robots)| Input {
-            map_size: map_size.unwrap_or(vec2(101, 103)),
-            robots,
+        .map(|(map_size, robots)| {
+            let map_size = map_size.unwrap_or(vec2(101, 103));
+            Input {
+                map_size,
+                floor: Map::filled(map_size.x as usize, map_size.y as usize, ()),
+                robots,
+            }
         })
 }
@@ -1,5 +1,5 @@
 use common::{
-    map::{Map, Vec2},
+    map::{grid_parser, FromGridChar, Map, Vec2},
     prelude::*,
 };
 
@@ -142,17 +142,20 @@ impl std::fmt::Display for Cell {
     }
 }
 
-fn map_parser_1() -> impl Parser<char, Map<Cell>, Error = Simple<char>> {
-    let cell = choice([
-        just(".").to(Cell::Empty),
-        just("#").to(Cell::Wall),
-        just("@").to(Cell::Robot),
-        just("O").to(Cell::Box),
-    ]);
-
-    let line = cell.repeated().at_least(1);
+impl FromGridChar for Cell {
+    fn from_grid_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '#' => Some(Cell::Wall),
+            '@' => Some(Cell::Robot),
+            'O' => Some(Cell::Box),
+            _ => None,
+        }
+    }
+}
 
-    line.separated_by(text::newline()).map(Map::new)
+fn map_parser_1() -> impl Parser<char, Map<Cell>, Error = Simple<char>> {
+    grid_parser()
 }
 
 fn map_parser_2() -> impl Parser<char, Map<Cell>, Error = Simple<char>> {
@@ -1,70 +1,82 @@
-use std::{collections::HashSet, f32::consts::FRAC_PI_2};
+use std::collections::HashSet;
 
 use common::{
-    map::{Map, Vec2},
+    debug,
+    map::{
+        render::{Color, Renderer},
+        search::dijkstra,
+        Direction, FromGridChar, Map, Vec2,
+    },
     prelude::*,
 };
 
-use chumsky::prelude::*;
 use itertools::Itertools;
-use pathfinding::directed::astar::astar_bag;
 use petgraph::{algo::astar, prelude::*};
 
-register_solver!(2024, 16, Solver);
 pub struct Solver;
 
+#[solver(
+    title = "Reindeer Maze",
+    description = "Dijkstra over (position, direction) states, collecting all best paths",
+    tags = [Grid, Graph],
+)]
 impl MonoDaySolver for Solver {
     fn solve(&self, input: &str) -> (PartResult, PartResult) {
-        let map = parser().parse(input).unwrap();
+        let (map, [start_pos, end_pos]) =
+            Map::<Cell>::parse_with_markers(input, ['S', 'E']).unwrap();
 
-        let start_pos = map.iter().find(|(_, c)| **c == Cell::Start).unwrap().0;
-        let end_pos = map.iter().find(|(_, c)| **c == Cell::End).unwrap().0;
-
-        let (solution, cost) = astar_bag(
-            &(start_pos, Vec2::EAST),
-            |(p, d)| {
-                let p = *p;
-                let d = *d;
+        let search = dijkstra(
+            [(start_pos, Direction::East)],
+            |&(p, d)| {
                 let mut edges = tinyvec::ArrayVec::<[_; 3]>::default();
                 if map.get(p + d).map(|c| c.is_path()).unwrap_or(false) {
                     edges.push(((p + d, d), 1));
                 }
-                edges.push(((p, d.rotate(FRAC_PI_2)), 1000));
-                edges.push(((p, d.rotate(-FRAC_PI_2)), 1000));
+                edges.push(((p, d.turn_right()), 1000));
+                edges.push(((p, d.turn_left()), 1000));
                 edges
             },
-            |_| 0,
-            |n| n.0 == end_pos,
-        )
-        .unwrap();
+            |&(p, _)| p == end_pos,
+        );
+
+        let best_tiles = search
+            .goals()
+            .iter()
+            .flat_map(|goal| search.states_on_shortest_paths(goal))
+            .map(|(p, _)| p)
+            .collect::<HashSet<_>>();
+
+        if debug::enabled() {
+            Renderer::new(&map)
+                .color_by(|c| (*c == Cell::Wall).then_some(Color::Gray))
+                .points(best_tiles.iter().copied(), 'O', Some(Color::Green))
+                .debug_print();
+        }
 
         (
-            PartResult::new(cost),
-            PartResult::new(
-                solution
-                    .flatten()
-                    .map(|(p, _)| p)
-                    .collect::<HashSet<_>>()
-                    .len(),
-            ),
+            PartResult::new(search.goal_distance().unwrap()),
+            PartResult::new(best_tiles.len()),
         )
     }
 }
 
-register_solver!(2024, 16, SolverPetgraph, "petgraph");
 pub struct SolverPetgraph;
 
+#[solver(
+    label = "petgraph",
+    title = "Reindeer Maze",
+    description = "Repeated A* on a petgraph graph, penalizing already found paths",
+    tags = [Grid, Graph],
+)]
 impl MonoDaySolver for SolverPetgraph {
     fn solve(&self, input: &str) -> (PartResult, PartResult) {
-        let map = parser().parse(input).unwrap();
+        let (map, [start_pos, end_pos]) =
+            Map::<Cell>::parse_with_markers(input, ['S', 'E']).unwrap();
 
         let graph = build_graph(&map);
 
-        let start_pos = map.iter().find(|(_, c)| **c == Cell::Start).unwrap().0;
-        let end_pos = map.iter().find(|(_, c)| **c == Cell::End).unwrap().0;
-
         let mut nodes = HashSet::new();
-        let mut edges: HashSet<((Vec2, Vec2), (Vec2, Vec2))> = HashSet::new();
+        let mut edges: HashSet<((Vec2, Direction), (Vec2, Direction))> = HashSet::new();
 
         // For part 2, we repeat A* algorithm in a loop.
         //
@@ -79,7 +91,7 @@ impl MonoDaySolver for SolverPetgraph {
         loop {
             let (cost, path) = astar(
                 &graph,
-                (start_pos, Vec2::EAST),
+                (start_pos, Direction::East),
                 |n| n.0 == end_pos,
                 |e| {
                     let bias = if edges.contains(&(e.source(), e.target())) {
@@ -110,11 +122,11 @@ impl MonoDaySolver for SolverPetgraph {
     }
 }
 
-fn build_graph(map: &Map<Cell>) -> GraphMap<(Vec2, Vec2), i64, Directed> {
-    let mut graph: GraphMap<(Vec2, Vec2), i64, Directed> = GraphMap::new();
+fn build_graph(map: &Map<Cell>) -> GraphMap<(Vec2, Direction), i64, Directed> {
+    let mut graph: GraphMap<(Vec2, Direction), i64, Directed> = GraphMap::new();
 
     for (p, _) in map.iter().filter(|(_, c)| c.is_path()) {
-        for d in [Vec2::NORTH, Vec2::EAST, Vec2::SOUTH, Vec2::WEST] {
+        for d in Direction::ALL {
             graph.add_node((p, d));
         }
     }
@@ -123,8 +135,8 @@ fn build_graph(map: &Map<Cell>) -> GraphMap<(Vec2, Vec2), i64, Directed> {
         if map.get(p + d).map(|c| c.is_path()).unwrap_or(false) {
             edges.push(((p, d), (p + d, d), 1));
         }
-        edges.push(((p, d), (p, d.rotate(FRAC_PI_2)), 1000));
-        edges.push(((p, d), (p, d.rotate(-FRAC_PI_2)), 1000));
+        edges.push(((p, d), (p, d.turn_right()), 1000));
+        edges.push(((p, d), (p, d.turn_left()), 1000));
     }
 
     for (from, to, weight) in edges {
@@ -157,17 +169,14 @@ impl std::fmt::Display for Cell {
     }
 }
 
-fn parser() -> impl Parser<char, Map<Cell>, Error = Simple<char>> {
-    let cell = choice([
-        just(".").to(Cell::Empty),
-        just("#").to(Cell::Wall),
-        just("S").to(Cell::Start),
-        just("E").to(Cell::End),
-    ]);
-
-    let line = cell.repeated().at_least(1);
-
-    line.separated_by(text::newline())
-        .map(Map::new)
-        .then_ignore(end())
+impl FromGridChar for Cell {
+    fn from_grid_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '#' => Some(Cell::Wall),
+            'S' => Some(Cell::Start),
+            'E' => Some(Cell::End),
+            _ => None,
+        }
+    }
 }
@@ -12,7 +12,7 @@ impl DualDaySolver for Solver {
 
         machine.run();
 
-        PartResult::new(machine.format_output())
+        PartResult::from_display(machine.format_output())
     }
 
     fn solve_2(&self, input: &str) -> PartResult {
@@ -1,13 +1,10 @@
-use std::collections::HashMap;
-
 use common::{
-    map::{vec2, Vec2},
+    map::{search::bfs, vec2, SparseMap, Vec2},
     math_helper::partition_point,
     prelude::*,
 };
 
 use chumsky::prelude::*;
-use pathfinding::prelude::astar;
 
 register_solver!(2024, 18, Solver);
 pub struct Solver;
@@ -34,29 +31,26 @@ impl DualDaySolver for Solver {
     }
 }
 
-fn solve(input: &Input, target: Vec2, steps: usize) -> Option<i32> {
+fn solve(input: &Input, target: Vec2, steps: usize) -> Option<u64> {
     let start_pos = vec2(0, 0);
 
-    astar(
-        &start_pos,
+    bfs(
+        [start_pos],
         |p| {
-            p.four_adjacent_iter()
-                .filter(|n| {
-                    let is_blocked = input.bytes.get(n).map(|i| *i < steps).unwrap_or(false);
-                    n.x >= 0 && n.x <= target.x && n.y >= 0 && n.y <= target.y && !is_blocked
-                })
-                .map(|n| (n, 1))
+            p.four_adjacent_iter().filter(move |n| {
+                let is_blocked = input.bytes.get(*n).map(|i| *i < steps).unwrap_or(false);
+                n.x >= 0 && n.x <= target.x && n.y >= 0 && n.y <= target.y && !is_blocked
+            })
         },
-        |_| 0,
         |p| *p == target,
     )
-    .map(|(_, cost)| cost)
+    .goal_distance()
 }
 
 #[derive(Debug)]
 struct Input {
     is_test: bool,
-    bytes: HashMap<Vec2, usize>,
+    bytes: SparseMap<usize>,
 }
 
 impl Input {
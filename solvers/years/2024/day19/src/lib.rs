@@ -1,5 +1,4 @@
-use cached::Cached;
-use common::prelude::*;
+use common::{memo::Memo, prelude::*};
 
 use chumsky::prelude::*;
 use itertools::Itertools;
@@ -9,18 +8,14 @@ pub struct Solver;
 
 impl MonoDaySolver for Solver {
     fn solve(&self, input: &str) -> (PartResult, PartResult) {
-        let input = Box::leak(Box::new(parser().parse(input).unwrap()));
+        let input = parser().parse(input).unwrap();
 
         let (a, b) = input
             .designs
             .iter()
-            .map(|d| {
-                VALIDATE.lock().unwrap().cache_clear();
-                validate(d, &input.patterns, 0)
-            })
+            .map(|d| validate(&mut Memo::new(), d, &input.patterns, 0))
             .tee();
 
-        VALIDATE.lock().unwrap().cache_clear();
         (
             PartResult::new(a.filter(|i| *i > 0).count()),
             PartResult::new(b.sum::<usize>()),
@@ -28,32 +23,33 @@ impl MonoDaySolver for Solver {
     }
 }
 
-#[cached::proc_macro::cached(
-    key = "(usize, usize)",
-    convert = r#"{ (target.len(), pattern_index) }"#
-)]
+/// Targets are suffixes of a single design, so their length is enough to
+/// identify them in `memo`.
 fn validate(
-    target: &'static [Color],
-    patterns: &'static [Vec<Color>],
+    memo: &mut Memo<(usize, usize), usize>,
+    target: &[Color],
+    patterns: &[Vec<Color>],
     pattern_index: usize,
 ) -> usize {
     if pattern_index >= patterns.len() {
         return 0;
     }
 
-    let pattern = patterns[pattern_index].as_slice();
+    memo.get_or_compute((target.len(), pattern_index), |memo, _| {
+        let pattern = patterns[pattern_index].as_slice();
 
-    let res = if pattern.len() <= target.len() && target.starts_with(pattern) {
-        if target.len() == pattern.len() {
-            1
+        let res = if pattern.len() <= target.len() && target.starts_with(pattern) {
+            if target.len() == pattern.len() {
+                1
+            } else {
+                validate(memo, &target[pattern.len()..], patterns, 0)
+            }
         } else {
-            validate(&target[pattern.len()..], patterns, 0)
-        }
-    } else {
-        0
-    };
+            0
+        };
 
-    res + validate(target, patterns, pattern_index + 1)
+        res + validate(memo, target, patterns, pattern_index + 1)
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]